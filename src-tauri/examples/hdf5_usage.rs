@@ -59,7 +59,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // 3. Obtener estructura del archivo (limitada para evitar output muy largo)
     println!("\n🌳 ESTRUCTURA DEL ARCHIVO (primeros niveles):");
-    match HDF5Analyzer::get_file_structure(file_path) {
+    match HDF5Analyzer::get_file_structure(file_path, None) {
         Ok(structure) => {
             println!("  Total grupos: {}", structure.total_groups);
             println!("  Total datasets: {}", structure.total_datasets);
@@ -81,21 +81,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("    {}: {}", key, value);
             }
             
-            if !hecras_data.geometry_data.is_empty() {
-                println!("  🏗️  Datos de geometría encontrados:");
-                for (dataset_path, data) in hecras_data.geometry_data.iter().take(3) {
-                    println!("    📄 {}", dataset_path);
-                    println!("       Elementos: {}", data.len());
-                    if !data.is_empty() {
-                        let sample_size = std::cmp::min(3, data.len());
-                        println!("       Muestra: {:?}...", &data[..sample_size]);
-                    }
+            for (group_name, group_data) in &hecras_data.groups {
+                if group_data.is_empty() {
+                    continue;
                 }
-            }
-            
-            if !hecras_data.results_data.is_empty() {
-                println!("  📈 Datos de resultados encontrados:");
-                for (dataset_path, data) in hecras_data.results_data.iter().take(3) {
+                println!("  📦 Grupo \"{}\":", group_name);
+                for (dataset_path, data) in group_data.iter().take(3) {
                     println!("    📄 {}", dataset_path);
                     println!("       Elementos: {}", data.len());
                     if !data.is_empty() {
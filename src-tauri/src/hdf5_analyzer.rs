@@ -1,344 +0,0 @@
-use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FileInfo {
-    pub name: String,
-    pub path: String,
-    pub size_mb: f64,
-    pub modified: DateTime<Utc>,
-    pub accessible: bool,
-    pub groups_count: usize,
-    pub datasets_count: usize,
-    pub error: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TreeNode {
-    pub name: String,
-    pub path: String,
-    pub node_type: String, // "group" or "dataset"
-    pub children: Vec<TreeNode>,
-    pub attributes: HashMap<String, String>,
-    pub shape: Option<Vec<usize>>,
-    pub dtype: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FileStructure {
-    pub file_path: String,
-    pub root: TreeNode,
-    pub total_groups: usize,
-    pub total_datasets: usize,
-    pub error: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct HecRasData {
-    pub file: String,
-    pub geometry_data: HashMap<String, Vec<f64>>,
-    pub results_data: HashMap<String, Vec<f64>>,
-    pub metadata: HashMap<String, String>,
-    pub extraction_summary: HashMap<String, usize>,
-}
-
-pub struct HDF5Analyzer;
-
-impl HDF5Analyzer {
-    pub fn new() -> Self {
-        Self
-    }
-
-    /// Obtiene información básica del archivo HDF5
-    pub fn get_file_info<P: AsRef<Path>>(file_path: P) -> Result<FileInfo> {
-        let path = file_path.as_ref();
-        let metadata = std::fs::metadata(path)
-            .with_context(|| format!("Failed to read file metadata: {}", path.display()))?;
-
-        let modified = DateTime::from(metadata.modified()
-            .with_context(|| "Failed to get modification time")?);
-
-        let info = FileInfo {
-            name: path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("unknown")
-                .to_string(),
-            path: path.to_string_lossy().to_string(),
-            size_mb: metadata.len() as f64 / (1024.0 * 1024.0),
-            modified,
-            accessible: path.extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| ext.to_lowercase() == "hdf" || ext.to_lowercase() == "h5")
-                .unwrap_or(false),
-            groups_count: 0, // TODO: Implementar con HDF5 nativo
-            datasets_count: 0, // TODO: Implementar con HDF5 nativo
-            error: if !path.exists() {
-                Some("File does not exist".to_string())
-            } else {
-                None
-            },
-        };
-
-        Ok(info)
-    }
-
-    /// Placeholder para contar elementos (implementar con HDF5 nativo)
-    fn _count_items_placeholder() -> Result<(usize, usize)> {
-        // TODO: Implementar con HDF5 nativo
-        Ok((0, 0))
-    }
-
-    /// Construye la estructura completa del archivo HDF5
-    pub fn get_file_structure<P: AsRef<Path>>(file_path: P) -> Result<FileStructure> {
-        let path = file_path.as_ref();
-
-        // TODO: Implementar con HDF5 nativo
-        let structure = FileStructure {
-            file_path: path.to_string_lossy().to_string(),
-            root: TreeNode {
-                name: "/".to_string(),
-                path: "/".to_string(),
-                node_type: "group".to_string(),
-                children: vec![
-                    TreeNode {
-                        name: "Geometry".to_string(),
-                        path: "/Geometry".to_string(),
-                        node_type: "group".to_string(),
-                        children: Vec::new(),
-                        attributes: HashMap::new(),
-                        shape: None,
-                        dtype: None,
-                    },
-                    TreeNode {
-                        name: "Results".to_string(),
-                        path: "/Results".to_string(),
-                        node_type: "group".to_string(),
-                        children: Vec::new(),
-                        attributes: HashMap::new(),
-                        shape: None,
-                        dtype: None,
-                    },
-                ],
-                attributes: HashMap::new(),
-                shape: None,
-                dtype: None,
-            },
-            total_groups: 3, // Root + Geometry + Results
-            total_datasets: 0,
-            error: if !path.exists() {
-                Some("File does not exist".to_string())
-            } else {
-                None
-            },
-        };
-
-        Ok(structure)
-    }
-
-    /// Placeholder para construir árbol (implementar con HDF5 nativo)
-    fn _build_tree_placeholder() -> Result<()> {
-        // TODO: Implementar con HDF5 nativo
-        Ok(())
-    }
-
-    /// Cuenta elementos en el árbol construido
-    fn count_tree_items(node: &TreeNode, groups: &mut usize, datasets: &mut usize) {
-        match node.node_type.as_str() {
-            "group" => *groups += 1,
-            "dataset" => *datasets += 1,
-            _ => {}
-        }
-
-        for child in &node.children {
-            Self::count_tree_items(child, groups, datasets);
-        }
-    }
-
-    /// Lista todos los datasets en el archivo
-    pub fn list_datasets<P: AsRef<Path>>(file_path: P) -> Result<Vec<String>> {
-        let _path = file_path.as_ref();
-
-        // TODO: Implementar con HDF5 nativo
-        // Por ahora devolvemos datasets comunes de HEC-RAS
-        let datasets = vec![
-            "/Results/2D/MaxWSE".to_string(),
-            "/Results/2D/MaxVel".to_string(),
-            "/Results/2D/MaxDepth".to_string(),
-            "/Geometry/2D Flow Areas/Area 2D/Cells Center Coordinate".to_string(),
-            "/Geometry/2D Flow Areas/Area 2D/Cells FacePoint Indexes".to_string(),
-        ];
-
-        Ok(datasets)
-    }
-
-    /// Placeholder para recolectar datasets (implementar con HDF5 nativo)
-    fn _collect_datasets_placeholder() -> Result<Vec<String>> {
-        // TODO: Implementar con HDF5 nativo
-        Ok(Vec::new())
-    }
-
-    /// Extrae datos específicos de HEC-RAS
-    pub fn extract_hecras_data<P: AsRef<Path>>(file_path: P) -> Result<HecRasData> {
-        let path = file_path.as_ref();
-
-        let mut hecras_data = HecRasData {
-            file: path.to_string_lossy().to_string(),
-            geometry_data: HashMap::new(),
-            results_data: HashMap::new(),
-            metadata: HashMap::new(),
-            extraction_summary: HashMap::new(),
-        };
-
-        // TODO: Implementar con HDF5 nativo
-        // Por ahora simulamos algunos datos
-        hecras_data.geometry_data.insert(
-            "/Geometry/2D Flow Areas/Area 2D/Cells Center Coordinate".to_string(),
-            vec![1.0, 2.0, 3.0, 4.0, 5.0]
-        );
-
-        hecras_data.results_data.insert(
-            "/Results/2D/MaxWSE".to_string(),
-            vec![10.5, 11.2, 9.8, 12.1, 10.9]
-        );
-
-        hecras_data.metadata.insert(
-            "File Type".to_string(),
-            "HEC-RAS Plan File".to_string()
-        );
-
-        hecras_data.metadata.insert(
-            "Version".to_string(),
-            "6.3.1".to_string()
-        );
-
-        // Generar resumen de extracción
-        hecras_data.extraction_summary.insert("geometry_datasets".to_string(), hecras_data.geometry_data.len());
-        hecras_data.extraction_summary.insert("results_datasets".to_string(), hecras_data.results_data.len());
-        hecras_data.extraction_summary.insert("metadata_items".to_string(), hecras_data.metadata.len());
-
-        Ok(hecras_data)
-    }
-
-    /// Placeholder para buscar datasets por patrón (implementar con HDF5 nativo)
-    fn _find_datasets_by_pattern_placeholder(_pattern: &str) -> Result<Option<Vec<String>>> {
-        // TODO: Implementar con HDF5 nativo
-        Ok(None)
-    }
-
-    /// Verifica si un path coincide con un patrón con wildcards
-    fn matches_pattern(path: &str, pattern: &str) -> bool {
-        let pattern_parts: Vec<&str> = pattern.split('/').collect();
-        let path_parts: Vec<&str> = path.split('/').collect();
-
-        if pattern_parts.len() != path_parts.len() {
-            return false;
-        }
-
-        for (pattern_part, path_part) in pattern_parts.iter().zip(path_parts.iter()) {
-            if *pattern_part != "*" && *pattern_part != *path_part {
-                return false;
-            }
-        }
-
-        true
-    }
-
-    /// Placeholder para leer dataset (implementar con HDF5 nativo)
-    fn _read_dataset_as_f64_placeholder(_dataset_path: &str) -> Result<Vec<f64>> {
-        // TODO: Implementar con HDF5 nativo
-        // Por ahora devolvemos datos simulados
-        Ok(vec![1.0, 2.0, 3.0, 4.0, 5.0])
-    }
-
-    /// Placeholder para extraer metadatos (implementar con HDF5 nativo)
-    fn _extract_metadata_placeholder() -> Result<HashMap<String, String>> {
-        // TODO: Implementar con HDF5 nativo
-        let mut metadata = HashMap::new();
-        metadata.insert("File Type".to_string(), "HEC-RAS Plan File".to_string());
-        metadata.insert("Version".to_string(), "6.3.1".to_string());
-        Ok(metadata)
-    }
-
-    /// Lee un dataset específico y devuelve una muestra de los datos
-    pub fn read_dataset_sample<P: AsRef<Path>>(file_path: P, dataset_path: &str, max_elements: usize) -> Result<Vec<f64>> {
-        let _path = file_path.as_ref();
-        let _dataset_path = dataset_path;
-
-        // TODO: Implementar con HDF5 nativo
-        // Por ahora devolvemos datos simulados
-        let mut data = vec![10.5, 11.2, 9.8, 12.1, 10.9, 11.5, 10.3, 12.8, 9.5, 11.8];
-
-        if data.len() > max_elements {
-            data.truncate(max_elements);
-        }
-
-        Ok(data)
-    }
-
-    /// Busca archivos HDF5 en una carpeta
-    pub fn find_hdf_files<P: AsRef<Path>>(folder_path: P) -> Result<Vec<FileInfo>> {
-        let path = folder_path.as_ref();
-        let mut hdf_files = Vec::new();
-
-        if !path.exists() {
-            return Err(anyhow::anyhow!("Folder does not exist: {}", path.display()));
-        }
-
-        if !path.is_dir() {
-            return Err(anyhow::anyhow!("Path is not a directory: {}", path.display()));
-        }
-
-        // Buscar archivos recursivamente
-        Self::search_hdf_files_recursive(path, &mut hdf_files)?;
-
-        // Ordenar por nombre de archivo
-        hdf_files.sort_by(|a, b| a.name.cmp(&b.name));
-
-        Ok(hdf_files)
-    }
-
-    /// Busca archivos HDF recursivamente
-    fn search_hdf_files_recursive(dir: &Path, files: &mut Vec<FileInfo>) -> Result<()> {
-        for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_dir() {
-                // Buscar recursivamente en subdirectorios
-                Self::search_hdf_files_recursive(&path, files)?;
-            } else if path.is_file() {
-                // Verificar si es un archivo HDF
-                if let Some(extension) = path.extension() {
-                    let ext = extension.to_string_lossy().to_lowercase();
-                    if ext == "hdf" || ext == "h5" || ext == "hdf5" {
-                        match Self::get_file_info(&path) {
-                            Ok(file_info) => files.push(file_info),
-                            Err(e) => {
-                                // Crear un FileInfo con error para archivos que no se pueden leer
-                                let file_info = FileInfo {
-                                    name: path.file_name()
-                                        .and_then(|n| n.to_str())
-                                        .unwrap_or("unknown")
-                                        .to_string(),
-                                    path: path.to_string_lossy().to_string(),
-                                    size_mb: 0.0,
-                                    modified: chrono::Utc::now(),
-                                    accessible: false,
-                                    groups_count: 0,
-                                    datasets_count: 0,
-                                    error: Some(format!("Error reading file: {}", e)),
-                                };
-                                files.push(file_info);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
-    }
-}
@@ -0,0 +1,199 @@
+//! Endianness-aware, zero-copy typed views over raw dataset bytes.
+//!
+//! HDF5 stores dataset elements as fixed-width, fixed-endian byte runs. Instead of
+//! copying each element into a `Vec<f64>` up front, these newtypes let a mapped byte
+//! slice be reinterpreted in place: the on-disk layout *is* the type's layout, so
+//! casting a `&[u8]` into a `&[F64Le]` costs an alignment check, not a copy.
+
+use anyhow::{bail, Result};
+use std::mem::{align_of, size_of};
+
+/// A type whose in-memory layout is bit-for-bit identical to its on-disk encoding,
+/// so a byte slice can be reinterpreted as `&[Self]` without copying.
+pub trait FromBytesLayout: Sized {
+    /// Reinterprets the leading bytes of `bytes` as `&[Self]`, returning the typed
+    /// slice plus whatever trailing bytes didn't form a whole element.
+    fn from_bytes(bytes: &[u8]) -> Result<(&[Self], &[u8])> {
+        let elem_size = size_of::<Self>();
+        if elem_size == 0 {
+            bail!("zero-sized element type");
+        }
+        if bytes.as_ptr().align_offset(align_of::<Self>()) != 0 {
+            bail!(
+                "buffer is not aligned to {} bytes for {}",
+                align_of::<Self>(),
+                std::any::type_name::<Self>()
+            );
+        }
+
+        let count = bytes.len() / elem_size;
+        let used = count * elem_size;
+        let (head, tail) = bytes.split_at(used);
+
+        // Safety: `Self` is `#[repr(transparent)]` over `[u8; elem_size]`, so any byte
+        // pattern is a valid value, alignment was just checked, and `head.len()` is an
+        // exact multiple of `elem_size`.
+        let typed = unsafe { std::slice::from_raw_parts(head.as_ptr().cast::<Self>(), count) };
+        Ok((typed, tail))
+    }
+}
+
+macro_rules! byte_view {
+    ($name:ident, $width:literal, $prim:ty, $from_bytes:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[repr(transparent)]
+        pub struct $name([u8; $width]);
+
+        impl $name {
+            /// Decodes this element into its native Rust representation.
+            pub fn get(&self) -> $prim {
+                <$prim>::$from_bytes(self.0)
+            }
+        }
+
+        impl FromBytesLayout for $name {}
+    };
+}
+
+byte_view!(F64Le, 8, f64, from_le_bytes, "Little-endian IEEE-754 double, as HDF5 writes it on x86/x64 platforms.");
+byte_view!(F64Be, 8, f64, from_be_bytes, "Big-endian IEEE-754 double.");
+byte_view!(F32Le, 4, f32, from_le_bytes, "Little-endian IEEE-754 single.");
+byte_view!(F32Be, 4, f32, from_be_bytes, "Big-endian IEEE-754 single.");
+byte_view!(U32Le, 4, u32, from_le_bytes, "Little-endian unsigned 32-bit integer.");
+byte_view!(U32Be, 4, u32, from_be_bytes, "Big-endian unsigned 32-bit integer.");
+byte_view!(U64Le, 8, u64, from_le_bytes, "Little-endian unsigned 64-bit integer.");
+byte_view!(U64Be, 8, u64, from_be_bytes, "Big-endian unsigned 64-bit integer.");
+
+/// The concrete element encoding of a dataset, resolved from its HDF5 datatype
+/// message. Every variant decodes to `f64` so callers can treat numeric datasets
+/// uniformly regardless of on-disk width or byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    F64Le,
+    F64Be,
+    F32Le,
+    F32Be,
+    /// HEC-RAS stores index arrays (e.g. face-point indices) as unsigned integers
+    /// rather than floats; these still decode to `f64` for a uniform call site.
+    U32Le,
+    U32Be,
+    U64Le,
+    U64Be,
+}
+
+impl ElementKind {
+    pub fn byte_width(self) -> usize {
+        match self {
+            ElementKind::F64Le | ElementKind::F64Be | ElementKind::U64Le | ElementKind::U64Be => 8,
+            ElementKind::F32Le | ElementKind::F32Be | ElementKind::U32Le | ElementKind::U32Be => 4,
+        }
+    }
+
+    /// Decodes up to `max_elements` values from the front of `bytes`, without
+    /// decoding any element past them.
+    pub fn decode_sample(self, bytes: &[u8], max_elements: usize) -> Result<Vec<f64>> {
+        Ok(self
+            .iter(bytes)?
+            .take(max_elements)
+            .collect())
+    }
+
+    /// Returns a lazy iterator over every element in `bytes`, decoding one element
+    /// at a time instead of materializing the whole dataset.
+    pub fn iter<'a>(self, bytes: &'a [u8]) -> Result<Box<dyn Iterator<Item = f64> + 'a>> {
+        Ok(match self {
+            ElementKind::F64Le => {
+                let (view, _) = F64Le::from_bytes(bytes)?;
+                Box::new(view.iter().map(F64Le::get))
+            }
+            ElementKind::F64Be => {
+                let (view, _) = F64Be::from_bytes(bytes)?;
+                Box::new(view.iter().map(F64Be::get))
+            }
+            ElementKind::F32Le => {
+                let (view, _) = F32Le::from_bytes(bytes)?;
+                Box::new(view.iter().map(|v| F32Le::get(v) as f64))
+            }
+            ElementKind::F32Be => {
+                let (view, _) = F32Be::from_bytes(bytes)?;
+                Box::new(view.iter().map(|v| F32Be::get(v) as f64))
+            }
+            ElementKind::U32Le => {
+                let (view, _) = U32Le::from_bytes(bytes)?;
+                Box::new(view.iter().map(|v| U32Le::get(v) as f64))
+            }
+            ElementKind::U32Be => {
+                let (view, _) = U32Be::from_bytes(bytes)?;
+                Box::new(view.iter().map(|v| U32Be::get(v) as f64))
+            }
+            ElementKind::U64Le => {
+                let (view, _) = U64Le::from_bytes(bytes)?;
+                Box::new(view.iter().map(|v| U64Le::get(v) as f64))
+            }
+            ElementKind::U64Be => {
+                let (view, _) = U64Be::from_bytes(bytes)?;
+                Box::new(view.iter().map(|v| U64Be::get(v) as f64))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_decodes_little_and_big_endian_the_same_value() {
+        let le = 1.5f64.to_le_bytes();
+        let be = 1.5f64.to_be_bytes();
+        let (le_view, _) = F64Le::from_bytes(&le).unwrap();
+        let (be_view, _) = F64Be::from_bytes(&be).unwrap();
+        assert_eq!(le_view[0].get(), 1.5);
+        assert_eq!(be_view[0].get(), 1.5);
+    }
+
+    #[test]
+    fn from_bytes_returns_leftover_bytes_that_dont_form_a_whole_element() {
+        let mut bytes = 7u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0xaa, 0xbb]);
+        let (view, tail) = U32Le::from_bytes(&bytes).unwrap();
+        assert_eq!(view.len(), 1);
+        assert_eq!(view[0].get(), 7);
+        assert_eq!(tail, &[0xaa, 0xbb]);
+    }
+
+    #[test]
+    fn from_bytes_errors_on_misaligned_input() {
+        // A slice starting one byte into a buffer is misaligned for any type wider
+        // than 1 byte on most allocations; skip if the allocator happened to give us
+        // an aligned buffer anyway.
+        let buf = vec![0u8; 17];
+        if buf.as_ptr().align_offset(align_of::<U64Le>()) == 0 {
+            assert!(U64Le::from_bytes(&buf[1..]).is_err());
+        }
+    }
+
+    #[test]
+    fn element_kind_byte_width_matches_its_backing_type() {
+        assert_eq!(ElementKind::F64Le.byte_width(), 8);
+        assert_eq!(ElementKind::F32Le.byte_width(), 4);
+        assert_eq!(ElementKind::U64Be.byte_width(), 8);
+        assert_eq!(ElementKind::U32Be.byte_width(), 4);
+    }
+
+    #[test]
+    fn decode_sample_caps_at_max_elements_without_reading_the_rest() {
+        let values: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let sample = ElementKind::F64Le.decode_sample(&bytes, 3).unwrap();
+        assert_eq!(sample, vec![0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn iter_decodes_every_element_in_order() {
+        let bytes: Vec<u8> = [1u32, 2, 3].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let decoded: Vec<f64> = ElementKind::U32Le.iter(&bytes).unwrap().collect();
+        assert_eq!(decoded, vec![1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,153 @@
+//! In-memory cache for parsed file structure, so repeated Tauri calls against the
+//! same open project file (tree view refresh, dataset picker, etc.) don't re-walk
+//! the whole HDF5 tree on every call.
+//!
+//! The cache key is the file's canonical path plus its modification time and size —
+//! cheap `stat()` facts that change whenever the file is rewritten, so a stale entry
+//! is never served after HEC-RAS finishes a run.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+
+use super::{FileStructure, HDF5Analyzer, HecRasData};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    canonical_path: std::path::PathBuf,
+    modified: SystemTime,
+    size: u64,
+}
+
+fn cache_key_for(path: &Path) -> Result<CacheKey> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("failed to stat {}", path.display()))?;
+    Ok(CacheKey {
+        canonical_path: std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()),
+        modified: metadata.modified().with_context(|| "failed to read modification time")?,
+        size: metadata.len(),
+    })
+}
+
+#[derive(Default)]
+struct CachedFile {
+    /// Keyed by the pattern each `FileStructure` was built with (`None` = unfiltered).
+    structures: HashMap<Option<String>, FileStructure>,
+    datasets: Option<Vec<String>>,
+    /// Paired with the extraction-profile layer mtimes the result was built from
+    /// (see `profile::layer_fingerprint`), since `extract_hecras_data`'s output
+    /// depends on those files too, not just the HDF5 file this entry is keyed on.
+    hecras_data: Option<(super::profile::ProfileFingerprint, HecRasData)>,
+}
+
+/// Holds cached parse results across Tauri calls. One instance is managed as Tauri
+/// state for the app's lifetime; nothing here is file-content-specific beyond the
+/// `(path, mtime, size)` key, so it's safe to share across every open project file.
+#[derive(Default)]
+pub struct AnalyzerSession {
+    entries: Mutex<HashMap<CacheKey, CachedFile>>,
+}
+
+impl AnalyzerSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same as `HDF5Analyzer::get_file_structure`, but served from cache when the
+    /// file's `(path, mtime, size)` hasn't changed since the last call. Pass
+    /// `bypass_cache: true` to force a fresh parse (and refresh the cache entry).
+    pub fn get_file_structure<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        pattern: Option<&str>,
+        bypass_cache: bool,
+    ) -> Result<FileStructure> {
+        let path = file_path.as_ref();
+        let key = cache_key_for(path)?;
+        let pattern_key = pattern.map(str::to_string);
+
+        if !bypass_cache {
+            let entries = self.entries.lock().unwrap();
+            if let Some(cached) = entries.get(&key).and_then(|f| f.structures.get(&pattern_key)) {
+                return Ok(cached.clone());
+            }
+        }
+
+        let structure = HDF5Analyzer::get_file_structure(path, pattern)?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.entry(key).or_default().structures.insert(pattern_key, structure.clone());
+        Ok(structure)
+    }
+
+    /// Same as `HDF5Analyzer::list_datasets`, cached the same way as
+    /// `get_file_structure`.
+    pub fn list_datasets<P: AsRef<Path>>(&self, file_path: P, bypass_cache: bool) -> Result<Vec<String>> {
+        let path = file_path.as_ref();
+        let key = cache_key_for(path)?;
+
+        if !bypass_cache {
+            let entries = self.entries.lock().unwrap();
+            if let Some(cached) = entries.get(&key).and_then(|f| f.datasets.clone()) {
+                return Ok(cached);
+            }
+        }
+
+        let datasets = HDF5Analyzer::list_datasets(path)?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.entry(key).or_default().datasets = Some(datasets.clone());
+        Ok(datasets)
+    }
+
+    /// Same as `HDF5Analyzer::extract_hecras_data`, cached the same way as
+    /// `get_file_structure`, but also invalidated when either extraction-profile
+    /// layer file for this HDF5 file has changed, since the result depends on those
+    /// too and they carry no mtime/size of their own in `CacheKey`.
+    pub fn extract_hecras_data<P: AsRef<Path>>(&self, file_path: P, bypass_cache: bool) -> Result<HecRasData> {
+        let path = file_path.as_ref();
+        let key = cache_key_for(path)?;
+        let fingerprint = super::profile::layer_fingerprint(path);
+
+        if !bypass_cache {
+            let entries = self.entries.lock().unwrap();
+            if let Some((cached_fingerprint, cached)) =
+                entries.get(&key).and_then(|f| f.hecras_data.as_ref())
+            {
+                if *cached_fingerprint == fingerprint {
+                    return Ok(cached.clone());
+                }
+            }
+        }
+
+        let hecras_data = HDF5Analyzer::extract_hecras_data(path)?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.entry(key).or_default().hecras_data = Some((fingerprint, hecras_data.clone()));
+        Ok(hecras_data)
+    }
+
+    /// Drops every cached entry for `file_path`, regardless of which `(mtime, size)`
+    /// it was cached under. Callers that know they just wrote the file (or want to
+    /// force a clean re-read) can use this instead of waiting for the mtime/size
+    /// key to naturally change.
+    ///
+    /// `canonicalize` is a live filesystem call, so it can fail here even though it
+    /// succeeded when the entry was cached (or vice versa) — comparing a raw
+    /// fallback path against a canonical one would then silently leave the stale
+    /// entry in place. If it fails, fall back to dropping every entry whose
+    /// canonical path has the same file name as `file_path`: invalidating more than
+    /// asked is harmless (the next read just re-parses), while failing to invalidate
+    /// means serving stale data from a file HEC-RAS just rewrote.
+    pub fn invalidate<P: AsRef<Path>>(&self, file_path: P) {
+        let file_path = file_path.as_ref();
+        let mut entries = self.entries.lock().unwrap();
+        match std::fs::canonicalize(file_path) {
+            Ok(target) => entries.retain(|key, _| key.canonical_path != target),
+            Err(_) => {
+                let name = file_path.file_name();
+                entries.retain(|key, _| key.canonical_path.file_name() != name);
+            }
+        }
+    }
+}
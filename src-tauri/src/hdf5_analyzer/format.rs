@@ -0,0 +1,753 @@
+//! Minimal native HDF5 binary-format reader.
+//!
+//! This is not a general-purpose HDF5 implementation — it understands the subset that
+//! HEC-RAS plan/results files actually use: a version 0 or 1 superblock, old-style
+//! group directories (v1 B-tree + local heap symbol tables), and contiguous dataset
+//! storage. Chunked/compressed layouts and the newer fractal-heap group format are out
+//! of scope; nodes that use them are still listed (so the tree doesn't silently lose
+//! entries) but are marked as unsupported instead of read.
+//!
+//! The file is memory-mapped once and every subsequent parse step borrows from that
+//! mapping, so opening a multi-gigabyte file and reading one small dataset only
+//! touches the pages that dataset actually lives on.
+
+use anyhow::{bail, Context, Result};
+use memmap2::Mmap;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::path::Path;
+
+use super::byteview::ElementKind;
+
+const SIGNATURE: [u8; 8] = [0x89, b'H', b'D', b'F', b'\r', b'\n', 0x1a, b'\n'];
+const UNDEFINED_ADDR: u64 = u64::MAX;
+
+/// A file mapped into memory for zero-copy parsing. Dropping this unmaps the file.
+pub struct MappedFile {
+    mmap: Mmap,
+}
+
+impl MappedFile {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("failed to open {} for mapping", path.display()))?;
+        // Safety: the mapping is read-only and only read while `self` (and the backing
+        // file) stays alive; we don't rely on the file being free of concurrent writers
+        // here — that's handled by the advisory lock layer above this module.
+        let mmap = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("failed to memory-map {}", path.display()))?;
+        if mmap.len() < 8 || mmap[0..8] != SIGNATURE {
+            bail!("{} is not an HDF5 file (bad signature)", path.display());
+        }
+        Ok(Self { mmap })
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    pub fn slice(&self, offset: u64, len: u64) -> Result<&[u8]> {
+        let start = usize::try_from(offset).context("offset too large for this platform")?;
+        let len = usize::try_from(len).context("length too large for this platform")?;
+        let end = start
+            .checked_add(len)
+            .context("dataset range overflows file bounds")?;
+        self.mmap
+            .get(start..end)
+            .with_context(|| format!("dataset range {start}..{end} is outside the mapped file"))
+    }
+}
+
+/// A group or dataset discovered while walking the file, before it's converted into
+/// the public `TreeNode` shape used by the rest of the analyzer.
+#[derive(Debug, Clone)]
+pub struct RawNode {
+    pub name: String,
+    pub path: String,
+    pub is_group: bool,
+    pub children: Vec<RawNode>,
+    pub attributes: HashMap<String, String>,
+    pub dataset: Option<DatasetLocation>,
+    /// For every group, expanded or not (see `Walker::recurse`), the B-tree/local-heap
+    /// address pair needed to read its children later; `None` for a dataset.
+    pub table_addr: Option<(u64, u64)>,
+}
+
+/// Where a contiguous dataset's bytes live, plus enough type information to decode
+/// them without touching the rest of the file.
+#[derive(Debug, Clone)]
+pub struct DatasetLocation {
+    pub offset: u64,
+    pub length: u64,
+    pub shape: Vec<usize>,
+    pub element_kind: Option<ElementKind>,
+    pub unsupported_reason: Option<String>,
+}
+
+struct Superblock {
+    size_of_offsets: u8,
+    size_of_lengths: u8,
+    root_btree_addr: u64,
+    root_heap_addr: u64,
+}
+
+/// Parses the superblock and walks the old-style (v1 B-tree) group hierarchy,
+/// producing a full tree of groups/datasets rooted at `/`.
+pub fn read_tree(mapped: &MappedFile) -> Result<RawNode> {
+    let sb = read_superblock(mapped)?;
+    let mut walker = Walker {
+        mapped,
+        sb: &sb,
+        recurse: true,
+    };
+    let children = walker.read_group_children_at(sb.root_btree_addr, sb.root_heap_addr)?;
+    Ok(RawNode {
+        name: "/".to_string(),
+        path: "/".to_string(),
+        is_group: true,
+        children,
+        attributes: HashMap::new(),
+        dataset: None,
+        table_addr: Some((sb.root_btree_addr, sb.root_heap_addr)),
+    })
+}
+
+/// Reads the superblock and the root group's immediate children only, without
+/// recursing into any subgroup — the entry point for pattern-based lookups that
+/// prune whole subtrees via `Matcher::visit_children_set` instead of enumerating them.
+pub fn read_root_children(mapped: &MappedFile) -> Result<Vec<RawNode>> {
+    let sb = read_superblock(mapped)?;
+    let mut walker = Walker {
+        mapped,
+        sb: &sb,
+        recurse: false,
+    };
+    walker.read_group_children_at(sb.root_btree_addr, sb.root_heap_addr)
+}
+
+/// Reads the children of a group previously returned with `recurse: false`, using
+/// its `table_addr`. With `recurse: false` only the immediate children are read, so
+/// a caller can expand one level at a time instead of materializing the whole tree
+/// up front; `recurse: true` reads the group's whole subtree.
+pub fn read_children_at(mapped: &MappedFile, table_addr: (u64, u64), recurse: bool) -> Result<Vec<RawNode>> {
+    let sb = read_superblock(mapped)?;
+    let mut walker = Walker { mapped, sb: &sb, recurse };
+    walker.read_group_children_at(table_addr.0, table_addr.1)
+}
+
+/// Navigates straight to the node at `path` (e.g. `/Results/2D/MaxWSE`), reading only
+/// the group directories along the way rather than the whole file tree. This is what
+/// makes single-dataset reads cheap on a multi-gigabyte file: only the B-tree, local
+/// heap, and object header for each path segment are ever mapped-and-read.
+pub fn locate_node(mapped: &MappedFile, path: &str) -> Result<RawNode> {
+    let sb = read_superblock(mapped)?;
+    let mut walker = Walker {
+        mapped,
+        sb: &sb,
+        recurse: false,
+    };
+
+    let segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    if segments.is_empty() {
+        bail!("'{path}' does not name a node");
+    }
+
+    let mut btree = sb.root_btree_addr;
+    let mut heap = sb.root_heap_addr;
+    let last = segments.len() - 1;
+    let mut found: Option<RawNode> = None;
+    for (i, segment) in segments.iter().enumerate() {
+        let children = walker.read_group_children_at(btree, heap)?;
+        let child = children
+            .into_iter()
+            .find(|c| &c.name == segment)
+            .with_context(|| format!("no such path '{path}' (missing segment '{segment}')"))?;
+        match child.table_addr {
+            Some(table_addr) => {
+                btree = table_addr.0;
+                heap = table_addr.1;
+            }
+            None if i != last => {
+                bail!(
+                    "no such path '{path}' ('{segment}' is a dataset, not a group, so '{}' cannot be under it)",
+                    segments[i + 1]
+                );
+            }
+            None => {}
+        }
+        found = Some(child);
+    }
+    found.context("empty path")
+}
+
+fn read_superblock(mapped: &MappedFile) -> Result<Superblock> {
+    let bytes = mapped.bytes();
+    let version = *bytes.get(8).context("truncated superblock")?;
+    match version {
+        0 | 1 => {
+            let size_of_offsets = *bytes.get(13).context("truncated superblock")?;
+            let size_of_lengths = *bytes.get(14).context("truncated superblock")?;
+            // Fixed fields up to the root group symbol table entry differ slightly
+            // between v0 (24 bytes of fixed header after the two size fields) and v1
+            // (adds 4 bytes for indexed storage K); the symbol table entry itself is
+            // always the last thing in the superblock.
+            let fixed_len = if version == 0 { 24 } else { 28 };
+            let entry_start = 16 + fixed_len;
+            let offsets = size_of_offsets as usize;
+            // A symbol table entry is: link name offset, object header address, cache
+            // type, reserved, then scratch-pad (btree addr, heap addr for type 1).
+            let oh_addr_off = entry_start + offsets;
+            let scratch_off = oh_addr_off + offsets + 4 + 4;
+            let root_btree_addr = read_offset(bytes, scratch_off, size_of_offsets)?;
+            let root_heap_addr = read_offset(bytes, scratch_off + offsets, size_of_offsets)?;
+            Ok(Superblock {
+                size_of_offsets,
+                size_of_lengths,
+                root_btree_addr,
+                root_heap_addr,
+            })
+        }
+        _ => bail!(
+            "superblock version {version} (v2/v3, fractal-heap groups) is not supported by this reader yet"
+        ),
+    }
+}
+
+/// Reads a `width`-byte little-endian offset/length field and widens it to `u64`.
+/// The on-disk "undefined address" sentinel is all-one-bits *within that width*
+/// (e.g. `0xFFFFFFFF` for a 4-byte field), not within a full 8 bytes, so that case
+/// is recognized and mapped to `UNDEFINED_ADDR` explicitly; every other value is
+/// zero-extended rather than padded with `0xff`, which would otherwise turn a
+/// legitimate small offset into a bogus giant address on any superblock narrower
+/// than 8 bytes.
+fn read_offset(bytes: &[u8], at: usize, width: u8) -> Result<u64> {
+    let slice = bytes
+        .get(at..at + width as usize)
+        .context("offset field runs past end of file")?;
+    if slice.iter().all(|&b| b == 0xff) {
+        return Ok(UNDEFINED_ADDR);
+    }
+    let mut buf = [0u8; 8];
+    buf[..slice.len()].copy_from_slice(slice);
+    Ok(u64::from_le_bytes(buf))
+}
+
+struct Walker<'a> {
+    mapped: &'a MappedFile,
+    sb: &'a Superblock,
+    /// Whether a group encountered while walking should have its own children read
+    /// immediately (`read_tree`) or left for the caller to expand on demand
+    /// (`locate_node`, `read_root_children`).
+    recurse: bool,
+}
+
+impl<'a> Walker<'a> {
+    fn read_group_children_at(&mut self, btree_addr: u64, heap_addr: u64) -> Result<Vec<RawNode>> {
+        if btree_addr == UNDEFINED_ADDR || heap_addr == UNDEFINED_ADDR {
+            return Ok(Vec::new());
+        }
+        let heap_data = self.read_local_heap_data(heap_addr)?;
+        let mut entries = Vec::new();
+        self.collect_symbol_entries(btree_addr, &mut entries)?;
+
+        let mut nodes = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let name = read_heap_string(heap_data, entry.name_offset)?;
+            nodes.push(self.read_object(&name, entry.object_header_addr, entry.cache_scratch)?);
+        }
+        Ok(nodes)
+    }
+
+    fn read_local_heap_data(&self, heap_addr: u64) -> Result<&'a [u8]> {
+        let bytes = self.mapped.bytes();
+        let sig = bytes
+            .get(heap_addr as usize..heap_addr as usize + 4)
+            .context("truncated local heap header")?;
+        if sig != b"HEAP" {
+            bail!("local heap at {heap_addr:#x} has a bad signature");
+        }
+        let offsets = self.sb.size_of_offsets as usize;
+        let lengths = self.sb.size_of_lengths as usize;
+        let data_addr_off = heap_addr as usize + 8 + lengths + lengths;
+        let data_addr = read_offset(bytes, data_addr_off, self.sb.size_of_offsets)?;
+        let data_seg_size = read_offset(bytes, heap_addr as usize + 8, self.sb.size_of_offsets)
+            .unwrap_or(0);
+        let _ = offsets;
+        let len = if data_seg_size > 0 { data_seg_size } else { 4096 };
+        self.mapped.slice(data_addr, len)
+    }
+
+    fn collect_symbol_entries(&self, btree_addr: u64, out: &mut Vec<SymbolEntry>) -> Result<()> {
+        let bytes = self.mapped.bytes();
+        let at = btree_addr as usize;
+        let sig = bytes.get(at..at + 4).context("truncated B-tree node")?;
+        if sig != b"TREE" {
+            bail!("group B-tree at {btree_addr:#x} has a bad signature");
+        }
+        let node_type = *bytes.get(at + 4).context("truncated B-tree node")?;
+        let node_level = *bytes.get(at + 5).context("truncated B-tree node")?;
+        if node_type != 0 {
+            bail!("B-tree node at {btree_addr:#x} is not a group (type {node_type})");
+        }
+        let entries_used = u16::from_le_bytes(
+            bytes
+                .get(at + 6..at + 8)
+                .context("truncated B-tree node")?
+                .try_into()
+                .context("truncated B-tree node")?,
+        );
+
+        let offsets = self.sb.size_of_offsets as usize;
+        let lengths = self.sb.size_of_lengths as usize;
+        // Header: signature(4) type(1) level(1) entries_used(2) left(offsets) right(offsets)
+        let mut pos = at + 8 + offsets + offsets;
+        // First key (a heap offset, `lengths` bytes) precedes the first child pointer.
+        pos += lengths;
+        for _ in 0..entries_used {
+            let child_addr = read_offset(bytes, pos, self.sb.size_of_offsets)?;
+            pos += offsets;
+            pos += lengths; // key following this child
+
+            if node_level == 0 {
+                self.collect_symbol_table_node(child_addr, out)?;
+            } else {
+                self.collect_symbol_entries(child_addr, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_symbol_table_node(&self, addr: u64, out: &mut Vec<SymbolEntry>) -> Result<()> {
+        let bytes = self.mapped.bytes();
+        let at = addr as usize;
+        let sig = bytes.get(at..at + 4).context("truncated symbol table node")?;
+        if sig != b"SNOD" {
+            bail!("symbol table node at {addr:#x} has a bad signature");
+        }
+        let num_symbols = u16::from_le_bytes(
+            bytes
+                .get(at + 6..at + 8)
+                .context("truncated symbol table node")?
+                .try_into()
+                .context("truncated symbol table node")?,
+        );
+
+        let offsets = self.sb.size_of_offsets as usize;
+        let lengths = self.sb.size_of_lengths as usize;
+        let mut pos = at + 8;
+        for _ in 0..num_symbols {
+            let name_offset = read_offset(bytes, pos, self.sb.size_of_offsets)?;
+            pos += offsets;
+            let object_header_addr = read_offset(bytes, pos, self.sb.size_of_offsets)?;
+            pos += offsets;
+            let cache_type = u32::from_le_bytes(
+                bytes
+                    .get(pos..pos + 4)
+                    .context("truncated symbol table entry")?
+                    .try_into()
+                    .context("truncated symbol table entry")?,
+            );
+            pos += 4 + 4; // cache type + reserved
+            let scratch_start = pos;
+            pos += 16; // scratch-pad is always 16 bytes regardless of cache type
+            let _ = lengths;
+
+            let cache_scratch = if cache_type == 1 {
+                let btree = read_offset(bytes, scratch_start, self.sb.size_of_offsets)?;
+                let heap = read_offset(bytes, scratch_start + offsets, self.sb.size_of_offsets)?;
+                Some((btree, heap))
+            } else {
+                None
+            };
+
+            out.push(SymbolEntry {
+                name_offset,
+                object_header_addr,
+                cache_scratch,
+            });
+        }
+        Ok(())
+    }
+
+    fn read_object(&mut self, name: &str, oh_addr: u64, cache_scratch: Option<(u64, u64)>) -> Result<RawNode> {
+        let header = parse_object_header(self.mapped, oh_addr, self.sb.size_of_offsets, self.sb.size_of_lengths)?;
+
+        if let Some((btree, heap)) = cache_scratch.or(header.symbol_table) {
+            let children = if self.recurse {
+                self.read_group_children_at(btree, heap)?
+            } else {
+                Vec::new()
+            };
+            return Ok(RawNode {
+                name: name.to_string(),
+                path: String::new(), // filled in by the caller once the parent path is known
+                is_group: true,
+                children,
+                attributes: header.attributes,
+                dataset: None,
+                table_addr: Some((btree, heap)),
+            });
+        }
+
+        let dataset = Some(DatasetLocation {
+            offset: header.layout.as_ref().map(|l| l.offset).unwrap_or(0),
+            length: header.layout.as_ref().map(|l| l.length).unwrap_or(0),
+            shape: header.shape.clone(),
+            element_kind: header.element_kind,
+            unsupported_reason: header.unsupported_reason.clone(),
+        });
+
+        Ok(RawNode {
+            name: name.to_string(),
+            path: String::new(),
+            is_group: false,
+            children: Vec::new(),
+            attributes: header.attributes,
+            dataset,
+            table_addr: None,
+        })
+    }
+}
+
+impl DatasetLocation {
+    /// Slices only the first `max_elements` elements' worth of this dataset's bytes
+    /// out of the file mapping and decodes them.
+    pub fn read_sample(&self, mapped: &MappedFile, max_elements: usize) -> Result<Vec<f64>> {
+        let kind = self
+            .element_kind
+            .context("dataset has a non-numeric or unrecognized datatype")?;
+        let want = (max_elements * kind.byte_width()) as u64;
+        let len = want.min(self.length);
+        let bytes = mapped.slice(self.offset, len)?;
+        kind.decode_sample(bytes, max_elements)
+    }
+
+    /// Maps the full dataset and returns a lazy element iterator over it, for
+    /// streaming consumers that don't want the whole thing materialized as a `Vec`.
+    pub fn read_stream<'a>(&self, mapped: &'a MappedFile) -> Result<Box<dyn Iterator<Item = f64> + 'a>> {
+        let kind = self
+            .element_kind
+            .context("dataset has a non-numeric or unrecognized datatype")?;
+        let bytes = mapped.slice(self.offset, self.length)?;
+        kind.iter(bytes)
+    }
+}
+
+struct SymbolEntry {
+    name_offset: u64,
+    object_header_addr: u64,
+    cache_scratch: Option<(u64, u64)>,
+}
+
+struct ContiguousLayout {
+    offset: u64,
+    length: u64,
+}
+
+struct ParsedHeader {
+    symbol_table: Option<(u64, u64)>,
+    shape: Vec<usize>,
+    element_kind: Option<ElementKind>,
+    layout: Option<ContiguousLayout>,
+    attributes: HashMap<String, String>,
+    unsupported_reason: Option<String>,
+}
+
+fn parse_object_header(
+    mapped: &MappedFile,
+    addr: u64,
+    size_of_offsets: u8,
+    size_of_lengths: u8,
+) -> Result<ParsedHeader> {
+    let bytes = mapped.bytes();
+    let at = addr as usize;
+    let version = *bytes.get(at).context("truncated object header")?;
+    if version != 1 {
+        bail!("object header version {version} (v2 headers) is not supported by this reader yet");
+    }
+    let num_messages = u16::from_le_bytes(
+        bytes
+            .get(at + 2..at + 4)
+            .context("truncated object header")?
+            .try_into()
+            .context("truncated object header")?,
+    );
+    let header_size = u32::from_le_bytes(
+        bytes
+            .get(at + 8..at + 12)
+            .context("truncated object header")?
+            .try_into()
+            .context("truncated object header")?,
+    );
+    let start = at + 16; // header is padded to an 8-byte boundary before messages start
+    let primary_end = start + header_size as usize;
+
+    let mut result = ParsedHeader {
+        symbol_table: None,
+        shape: Vec::new(),
+        element_kind: None,
+        layout: None,
+        attributes: HashMap::new(),
+        unsupported_reason: None,
+    };
+
+    // A v1 object header's messages can spill into one or more continuation blocks
+    // (message type 0x0010) elsewhere in the file; HEC-RAS files routinely need this
+    // once enough attributes push the dataspace/datatype/layout messages past the
+    // primary block. Each entry is a `(pos, end)` byte range still to scan.
+    let mut blocks: VecDeque<(usize, usize)> = VecDeque::from([(start, primary_end)]);
+    let mut seen = 0u16;
+    while let Some((mut pos, end)) = blocks.pop_front() {
+        while pos + 8 <= end && seen < num_messages {
+            let msg_type = u16::from_le_bytes(
+                bytes
+                    .get(pos..pos + 2)
+                    .context("truncated object header message")?
+                    .try_into()
+                    .context("truncated object header message")?,
+            );
+            let msg_size = u16::from_le_bytes(
+                bytes
+                    .get(pos + 2..pos + 4)
+                    .context("truncated object header message")?
+                    .try_into()
+                    .context("truncated object header message")?,
+            ) as usize;
+            let body_start = pos + 8;
+            let body = bytes
+                .get(body_start..body_start + msg_size)
+                .context("object header message runs past end of file")?;
+
+            match msg_type {
+                0x0001 => result.shape = parse_dataspace_message(body)?,
+                0x0003 => result.element_kind = parse_datatype_message(body),
+                0x0008 => match parse_layout_message(body, size_of_offsets, size_of_lengths)? {
+                    Some(layout) => result.layout = Some(layout),
+                    None => {
+                        result.unsupported_reason =
+                            Some("chunked or compressed storage is not supported yet".to_string())
+                    }
+                },
+                0x0010 => {
+                    let cont_offset = read_offset(body, 0, size_of_offsets)?;
+                    let cont_length = read_offset(body, size_of_offsets as usize, size_of_lengths)?;
+                    let cont_start =
+                        usize::try_from(cont_offset).context("continuation offset too large for this platform")?;
+                    let cont_len =
+                        usize::try_from(cont_length).context("continuation length too large for this platform")?;
+                    blocks.push_back((cont_start, cont_start + cont_len));
+                }
+                0x0011 => {
+                    let btree = read_offset(body, 0, size_of_offsets)?;
+                    let heap = read_offset(body, size_of_offsets as usize, size_of_offsets)?;
+                    result.symbol_table = Some((btree, heap));
+                }
+                0x000c => {
+                    if let Some((name, value)) = parse_attribute_message(body) {
+                        result.attributes.insert(name, value);
+                    }
+                }
+                _ => {}
+            }
+
+            pos = body_start + msg_size;
+            seen += 1;
+        }
+    }
+
+    if seen < num_messages {
+        bail!(
+            "object header at {addr:#x} only yielded {seen} of {num_messages} declared messages \
+             (ran out of continuation blocks) — refusing to return a header that looks complete \
+             but may be missing its dataspace/datatype/layout message"
+        );
+    }
+
+    Ok(result)
+}
+
+fn parse_dataspace_message(body: &[u8]) -> Result<Vec<usize>> {
+    if body.len() < 4 {
+        return Ok(Vec::new());
+    }
+    let version = body[0];
+    let rank = body[1] as usize;
+    let dims_start = if version == 1 { 8 } else { 4 };
+    let mut dims = Vec::with_capacity(rank);
+    for i in 0..rank {
+        let off = dims_start + i * 8;
+        let Some(slice) = body.get(off..off + 8) else {
+            break;
+        };
+        let v = u64::from_le_bytes(slice.try_into()?);
+        dims.push(v as usize);
+    }
+    Ok(dims)
+}
+
+fn parse_datatype_message(body: &[u8]) -> Option<ElementKind> {
+    if body.len() < 8 {
+        return None;
+    }
+    let class_and_version = *body.first()?;
+    let class = class_and_version & 0x0f;
+    let size = u32::from_le_bytes(body.get(4..8)?.try_into().ok()?);
+    // Bit 0 of the class bit-field (byte 1) is the byte-order flag: 0 = little-endian.
+    let big_endian = body.get(1)? & 0x01 != 0;
+
+    const FIXED_POINT: u8 = 0;
+    const FLOATING_POINT: u8 = 1;
+    match class {
+        FLOATING_POINT => match (size, big_endian) {
+            (8, false) => Some(ElementKind::F64Le),
+            (8, true) => Some(ElementKind::F64Be),
+            (4, false) => Some(ElementKind::F32Le),
+            (4, true) => Some(ElementKind::F32Be),
+            _ => None,
+        },
+        // HEC-RAS writes index arrays (face-point indices, cell counts) as
+        // unsigned fixed-point; sign is ignored here rather than risking a bogus
+        // negative cast for the handful of tables that might be signed.
+        FIXED_POINT => match (size, big_endian) {
+            (4, false) => Some(ElementKind::U32Le),
+            (4, true) => Some(ElementKind::U32Be),
+            (8, false) => Some(ElementKind::U64Le),
+            (8, true) => Some(ElementKind::U64Be),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Decodes a v1 attribute message's name and value, for the common case HEC-RAS
+/// writes: a scalar fixed-length string. Numeric and array-valued attributes are
+/// skipped rather than guessed at.
+fn parse_attribute_message(body: &[u8]) -> Option<(String, String)> {
+    if body.len() < 8 {
+        return None;
+    }
+    let name_size = u16::from_le_bytes(body.get(2..4)?.try_into().ok()?) as usize;
+    let dtype_size = u16::from_le_bytes(body.get(4..6)?.try_into().ok()?) as usize;
+    let dspace_size = u16::from_le_bytes(body.get(6..8)?.try_into().ok()?) as usize;
+
+    let pad8 = |n: usize| (n + 7) & !7;
+    let name_start = 8;
+    let name_end = name_start + name_size;
+    let name_bytes = body.get(name_start..name_end)?;
+    let name = String::from_utf8_lossy(name_bytes)
+        .trim_end_matches('\0')
+        .to_string();
+
+    let dtype_start = name_start + pad8(name_size);
+    let dtype_body = body.get(dtype_start..dtype_start + dtype_size)?;
+    let is_string = dtype_body.first().map(|b| b & 0x0f == 3).unwrap_or(false);
+    if !is_string {
+        return None;
+    }
+
+    let dspace_start = dtype_start + pad8(dtype_size);
+    let value_start = dspace_start + pad8(dspace_size);
+    let value_bytes = body.get(value_start..)?;
+    let value = String::from_utf8_lossy(value_bytes)
+        .trim_end_matches('\0')
+        .to_string();
+
+    Some((name, value))
+}
+
+fn parse_layout_message(
+    body: &[u8],
+    size_of_offsets: u8,
+    size_of_lengths: u8,
+) -> Result<Option<ContiguousLayout>> {
+    let Some(&version) = body.first() else {
+        return Ok(None);
+    };
+    if version != 3 {
+        // Versions 1/2 use a dimensionality-prefixed layout; not needed for the
+        // HEC-RAS files this reader targets.
+        return Ok(None);
+    }
+    let Some(&class) = body.get(1) else {
+        return Ok(None);
+    };
+    const CONTIGUOUS: u8 = 1;
+    if class != CONTIGUOUS {
+        return Ok(None);
+    }
+    let offset = read_offset(body, 2, size_of_offsets)?;
+    let length = read_offset(body, 2 + size_of_offsets as usize, size_of_lengths)?;
+    Ok(Some(ContiguousLayout { offset, length }))
+}
+
+fn read_heap_string(heap_data: &[u8], offset: u64) -> Result<String> {
+    let start = offset as usize;
+    let rest = heap_data
+        .get(start..)
+        .context("heap string offset runs past end of local heap data")?;
+    let end = rest.iter().position(|&b| b == 0).map(|p| start + p).unwrap_or(heap_data.len());
+    Ok(String::from_utf8_lossy(&heap_data[start..end]).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_offset_zero_extends_narrow_widths_instead_of_padding_with_ff() {
+        // A 4-byte field holding a small, legitimate offset must not turn into a
+        // giant bogus address just because it's narrower than the 8-byte u64 it's
+        // widened into (the bug fixed in d1122ed).
+        let bytes = [0x10, 0x00, 0x00, 0x00];
+        assert_eq!(read_offset(&bytes, 0, 4).unwrap(), 0x10);
+    }
+
+    #[test]
+    fn read_offset_recognizes_undefined_sentinel_within_its_own_width() {
+        // All-one-bits within a narrow field is HDF5's "undefined address" sentinel,
+        // even though it doesn't fill all 8 bytes of the widened u64.
+        let bytes = [0xff, 0xff, 0xff, 0xff];
+        assert_eq!(read_offset(&bytes, 0, 4).unwrap(), UNDEFINED_ADDR);
+    }
+
+    #[test]
+    fn read_offset_reads_full_8_byte_width() {
+        let bytes = 0x0102_0304_0506_0708u64.to_le_bytes();
+        assert_eq!(read_offset(&bytes, 0, 8).unwrap(), 0x0102_0304_0506_0708);
+    }
+
+    #[test]
+    fn read_offset_respects_a_nonzero_start_position() {
+        let bytes = [0xaa, 0xaa, 0x10, 0x00, 0x00, 0x00];
+        assert_eq!(read_offset(&bytes, 2, 4).unwrap(), 0x10);
+    }
+
+    #[test]
+    fn read_offset_errors_instead_of_panicking_on_a_truncated_file() {
+        let bytes = [0x01, 0x02];
+        assert!(read_offset(&bytes, 0, 4).is_err());
+    }
+
+    #[test]
+    fn read_heap_string_stops_at_the_nul_terminator() {
+        let heap = b"Area 2D\0garbage-after-terminator";
+        assert_eq!(read_heap_string(heap, 0).unwrap(), "Area 2D");
+    }
+
+    #[test]
+    fn read_heap_string_runs_to_the_end_when_unterminated() {
+        let heap = b"no terminator here";
+        assert_eq!(read_heap_string(heap, 0).unwrap(), "no terminator here");
+    }
+
+    #[test]
+    fn read_heap_string_errors_on_an_offset_past_the_heap() {
+        let heap = b"short";
+        assert!(read_heap_string(heap, 100).is_err());
+    }
+}
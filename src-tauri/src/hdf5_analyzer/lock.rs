@@ -0,0 +1,242 @@
+//! Detecting an in-progress HEC-RAS write, plus cooperative locking between eFlow's
+//! own concurrent reads of the same file.
+//!
+//! HEC-RAS rewrites these files in place while a simulation runs, so reading one
+//! mid-write can yield torn geometry/results — and HEC-RAS itself takes no OS lock
+//! we could wait on. The only externally-visible sign of a live writer is the file
+//! churning, so when asked to (`check_churn: true`), `acquire` samples size+mtime
+//! twice a beat apart and treats any change as "HEC-RAS is writing this right now"
+//! (`LockError::LikelyWriting`). That check is what actually protects against the
+//! real simulation process; it is a heuristic, not a guarantee, so a writer that
+//! happens to pause for exactly the sampling window can still slip through. It also
+//! costs a flat `WRITE_CHURN_WINDOW` sleep, so callers only ask for it on bulk reads
+//! where a torn result would actually matter (`extract_hecras_data`); a single
+//! dataset sample or pattern lookup isn't worth the latency. Once the file looks
+//! quiescent (or churn-checking wasn't requested), eFlow additionally takes a
+//! sidecar `<file>.eflowlock` via `O_CREAT|O_EXCL` semantics purely to stop two of its
+//! own Tauri commands from reading the same file at once — atomic creation means two
+//! eFlow readers can't both believe they hold it, but this half never contends with
+//! HEC-RAS itself.
+
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::format::MappedFile;
+
+const STALE_RETRY_ATTEMPTS: u32 = 5;
+const STALE_RETRY_DELAY: Duration = Duration::from_millis(20);
+/// How long to watch the file for size/mtime churn before concluding something is
+/// actively writing it. Long enough to catch HEC-RAS's typical write cadence, short
+/// enough that every read doesn't pay a noticeable delay.
+const WRITE_CHURN_WINDOW: Duration = Duration::from_millis(150);
+
+/// Why `FileLock::acquire` couldn't hand back a lock.
+#[derive(Debug)]
+pub enum LockError {
+    /// The file's size or mtime changed across `WRITE_CHURN_WINDOW`, i.e. something
+    /// outside eFlow — almost certainly a running HEC-RAS simulation — is writing it
+    /// right now.
+    LikelyWriting,
+    /// Another eFlow read already holds the cooperative sidecar lock; `holder`/`pid`
+    /// are whatever identity it wrote into the lock file (best-effort — a corrupt
+    /// lock file reports "unknown"/0).
+    AlreadyHeld { holder: String, pid: u32 },
+    Io(std::io::Error),
+    Read(anyhow::Error),
+}
+
+impl std::fmt::Display for LockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockError::LikelyWriting => write!(
+                f,
+                "file is in use by HEC-RAS (still being written) — try again once the run finishes"
+            ),
+            LockError::AlreadyHeld { holder, pid } => write!(
+                f,
+                "file is already being read by another eFlow operation (pid {pid} on {holder}) — try again shortly"
+            ),
+            LockError::Io(e) => write!(f, "failed to acquire file lock: {e}"),
+            LockError::Read(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for LockError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LockError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LockError {
+    fn from(e: std::io::Error) -> Self {
+        LockError::Io(e)
+    }
+}
+
+/// An acquired advisory lock. Dropping it (including during a panic unwind) removes
+/// the sidecar lock file, so callers don't need any explicit cleanup path.
+pub struct FileLock {
+    lock_path: PathBuf,
+}
+
+impl FileLock {
+    /// Attempts to acquire the lock without waiting. Pass `check_churn: true` to
+    /// first check whether the file itself looks like it's being actively written
+    /// (see module docs), returning `LockError::LikelyWriting` before ever touching
+    /// the sidecar lock — callers that only touch a narrow slice of the file (a
+    /// single dataset sample, a pattern lookup) skip this, since it's a flat
+    /// `WRITE_CHURN_WINDOW` sleep no matter how little they're about to read; it's
+    /// reserved for bulk reads like `extract_hecras_data` where a torn read is both
+    /// likely and expensive to have produced. If the sidecar lock is already held by
+    /// a dead process on this machine, reclaims it and retries immediately; a lock
+    /// held by a live process is retried with backoff up to `STALE_RETRY_ATTEMPTS`
+    /// times before giving up with `LockError::AlreadyHeld`.
+    pub fn acquire(target: &Path, check_churn: bool) -> Result<Self, LockError> {
+        if check_churn && is_actively_writing(target)? {
+            return Err(LockError::LikelyWriting);
+        }
+
+        let lock_path = sidecar_path(target);
+        let identity = current_identity();
+        let mut attempt = 0;
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&lock_path) {
+                Ok(mut file) => {
+                    file.write_all(identity.encode().as_bytes())?;
+                    return Ok(Self { lock_path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let holder = read_identity(&lock_path);
+                    let is_stale = holder
+                        .as_ref()
+                        .map(|h| h.hostname == identity.hostname && !process_is_alive(h.pid))
+                        .unwrap_or(false);
+
+                    if is_stale {
+                        // The lock we're about to report as "held" belongs to a dead
+                        // process: remove it and retry creating right away, instead of
+                        // counting this round against the live-holder retry budget
+                        // below and risking reporting a lock that no longer exists.
+                        let _ = fs::remove_file(&lock_path);
+                        continue;
+                    }
+
+                    if attempt >= STALE_RETRY_ATTEMPTS {
+                        let (holder, pid) = holder
+                            .map(|h| (h.hostname, h.pid))
+                            .unwrap_or_else(|| ("unknown".to_string(), 0));
+                        return Err(LockError::AlreadyHeld { holder, pid });
+                    }
+                    attempt += 1;
+                    std::thread::sleep(STALE_RETRY_DELAY);
+                }
+                Err(e) => return Err(LockError::Io(e)),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.lock_path);
+    }
+}
+
+/// A memory-mapped HDF5 file held together with the advisory lock that protects it —
+/// the lock outlives the mapping for exactly as long as this value is alive, on
+/// every exit path (normal return, `?`, or panic unwind).
+pub struct LockedMappedFile {
+    mapped: MappedFile,
+    _lock: FileLock,
+}
+
+impl LockedMappedFile {
+    /// See `FileLock::acquire` for what `check_churn` controls.
+    pub fn open(path: &Path, check_churn: bool) -> Result<Self, LockError> {
+        let lock = FileLock::acquire(path, check_churn)?;
+        let mapped = MappedFile::open(path).map_err(LockError::Read)?;
+        Ok(Self { mapped, _lock: lock })
+    }
+
+    pub fn mapped(&self) -> &MappedFile {
+        &self.mapped
+    }
+}
+
+struct Identity {
+    hostname: String,
+    pid: u32,
+}
+
+impl Identity {
+    fn encode(&self) -> String {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        format!("{}\npid={}\ntimestamp={}\n", self.hostname, self.pid, timestamp)
+    }
+}
+
+fn current_identity() -> Identity {
+    Identity {
+        hostname: local_hostname(),
+        pid: std::process::id(),
+    }
+}
+
+fn local_hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_else(|_| "unknown-host".to_string())
+}
+
+fn read_identity(lock_path: &Path) -> Option<Identity> {
+    let mut contents = String::new();
+    std::fs::File::open(lock_path).ok()?.read_to_string(&mut contents).ok()?;
+    let mut lines = contents.lines();
+    let hostname = lines.next()?.to_string();
+    let pid = lines
+        .find_map(|l| l.strip_prefix("pid="))
+        .and_then(|p| p.parse().ok())?;
+    Some(Identity { hostname, pid })
+}
+
+/// Samples `target`'s size and mtime twice, `WRITE_CHURN_WINDOW` apart, and reports
+/// whether either changed — the only signal we have that a process outside eFlow
+/// (HEC-RAS, in practice) is writing the file right now.
+fn is_actively_writing(target: &Path) -> std::io::Result<bool> {
+    let before = fs::metadata(target)?;
+    std::thread::sleep(WRITE_CHURN_WINDOW);
+    let after = fs::metadata(target)?;
+    Ok(before.len() != after.len() || before.modified().ok() != after.modified().ok())
+}
+
+fn sidecar_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(".eflowlock");
+    target.with_file_name(name)
+}
+
+#[cfg(unix)]
+fn process_is_alive(pid: u32) -> bool {
+    // Signal 0 sends nothing but still validates the pid: ESRCH means no such
+    // process, anything else (including success or EPERM for another user's
+    // process) means it's alive.
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 || std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH) }
+}
+
+#[cfg(not(unix))]
+fn process_is_alive(_pid: u32) -> bool {
+    // No cheap liveness probe without an extra dependency on this platform; treat
+    // the holder as alive so a stuck lock requires manual cleanup rather than a
+    // false reclaim that could race a still-running HEC-RAS process.
+    true
+}
@@ -0,0 +1,463 @@
+//! Dataset path matching, with recursive `**` globs, `{a,b}` alternation, and
+//! traversal pruning so large files don't have to be enumerated in full to answer
+//! a narrow query like `/Geometry/2D Flow Areas/**`.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use super::format::{self, MappedFile, RawNode};
+
+/// What a tree walker should do with a group's children once it knows the pattern
+/// being matched and the path of the group itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VisitChildrenSet {
+    /// No child of this group can possibly match; skip the whole subtree without
+    /// reading so much as its symbol table.
+    Empty,
+    /// The group itself is the full match target; there's nothing further to
+    /// descend into.
+    This,
+    /// Any child, at any depth, might match (a `**` segment is in play here) —
+    /// enumerate and recurse into everything.
+    Recursive,
+    /// Only children with one of these exact names can lead to a match.
+    Set(HashSet<String>),
+}
+
+/// A compiled dataset-path pattern. `matches` tests a full path; `visit_children_set`
+/// lets a tree walker prune subtrees that can't contain a match instead of
+/// enumerating every node.
+pub trait Matcher {
+    fn matches(&self, path: &str) -> bool;
+    fn visit_children_set(&self, dir_path: &str) -> VisitChildrenSet;
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `**` — matches zero or more path segments.
+    DoubleStar,
+    /// One or more alternatives (from `{a,b}` expansion; a single-element vec for
+    /// a plain segment), each possibly containing `*`/`?` wildcards.
+    Alternatives(Vec<String>),
+}
+
+/// A glob pattern compiled into matchable segments, e.g. `/Results/**/MaxWSE` or
+/// `/Geometry/2D Flow Areas/{Area 2D,Area 3D}/*`.
+#[derive(Debug, Clone)]
+pub struct GlobMatcher {
+    segments: Vec<Segment>,
+}
+
+impl GlobMatcher {
+    pub fn compile(pattern: &str) -> Self {
+        let segments = pattern
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|raw| {
+                if raw == "**" {
+                    Segment::DoubleStar
+                } else {
+                    Segment::Alternatives(expand_braces(raw))
+                }
+            })
+            .collect();
+        Self { segments }
+    }
+}
+
+impl Matcher for GlobMatcher {
+    fn matches(&self, path: &str) -> bool {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+        match_segments(&self.segments, &segments)
+    }
+
+    fn visit_children_set(&self, dir_path: &str) -> VisitChildrenSet {
+        let path_segments: Vec<&str> =
+            dir_path.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+        let states = self.states_after(&path_segments);
+        if states.is_empty() {
+            return VisitChildrenSet::Empty;
+        }
+
+        // A `**` still "live" at this depth can consume any number of further
+        // segments, so every descendant must be visited regardless of name.
+        let has_double_star = states
+            .iter()
+            .any(|&s| s < self.segments.len() && matches!(self.segments[s], Segment::DoubleStar));
+        if has_double_star {
+            return VisitChildrenSet::Recursive;
+        }
+
+        let full_match = states.contains(&self.segments.len());
+        let mut names = HashSet::new();
+        let mut has_wild = false;
+        for &s in &states {
+            if s == self.segments.len() {
+                continue;
+            }
+            if let Segment::Alternatives(alts) = &self.segments[s] {
+                if alts.iter().any(|alt| has_wildcard(alt)) {
+                    has_wild = true;
+                } else {
+                    names.extend(alts.iter().cloned());
+                }
+            }
+        }
+
+        if has_wild {
+            // Can't enumerate a wildcard's matches without reading names; the only
+            // safe thing is to visit everything and let `matches()` filter leaves.
+            VisitChildrenSet::Recursive
+        } else if full_match && names.is_empty() {
+            VisitChildrenSet::This
+        } else if full_match {
+            // One branch of the pattern is already fully satisfied by this group
+            // while another still expects a specific child name below it — union
+            // as Recursive rather than silently dropping the satisfied branch.
+            VisitChildrenSet::Recursive
+        } else if names.is_empty() {
+            VisitChildrenSet::Empty
+        } else {
+            VisitChildrenSet::Set(names)
+        }
+    }
+}
+
+impl GlobMatcher {
+    /// Simulates the pattern as an NFA over `path_segments`, returning the set of
+    /// pattern positions reachable after consuming them all. A `**` segment can
+    /// match zero or more path segments, so more than one position may be live at
+    /// once (e.g. still "inside" the `**` and also past it) — collapsing to a
+    /// single depth index, as a plain index walk would, loses the positions that
+    /// stay open across multiple directory levels.
+    fn states_after(&self, path_segments: &[&str]) -> HashSet<usize> {
+        let mut states = self.epsilon_closure(&HashSet::from([0]));
+        for segment in path_segments {
+            states = self.epsilon_closure(&self.advance(&states, segment));
+        }
+        states
+    }
+
+    /// A `**` at position `s` can match zero segments, so position `s + 1` is
+    /// reachable without consuming anything — add that (and transitively, any
+    /// further leading `**`s) to the state set.
+    fn epsilon_closure(&self, states: &HashSet<usize>) -> HashSet<usize> {
+        let mut closure = states.clone();
+        loop {
+            let additions: Vec<usize> = closure
+                .iter()
+                .filter(|&&s| s < self.segments.len() && matches!(self.segments[s], Segment::DoubleStar))
+                .map(|&s| s + 1)
+                .filter(|next| !closure.contains(next))
+                .collect();
+            if additions.is_empty() {
+                return closure;
+            }
+            closure.extend(additions);
+        }
+    }
+
+    fn advance(&self, states: &HashSet<usize>, segment: &str) -> HashSet<usize> {
+        let mut next = HashSet::new();
+        for &s in states {
+            if s >= self.segments.len() {
+                continue;
+            }
+            match &self.segments[s] {
+                Segment::DoubleStar => {
+                    next.insert(s);
+                }
+                Segment::Alternatives(alts) => {
+                    if alts.iter().any(|alt| glob_match(alt, segment)) {
+                        next.insert(s + 1);
+                    }
+                }
+            }
+        }
+        next
+    }
+}
+
+/// Matches everything and never prunes — used to drive the shared tree walker when
+/// no pattern filter was requested, so `get_file_structure`/`list_datasets` and
+/// `find_datasets_by_pattern` share one traversal implementation.
+pub struct MatchAll;
+
+impl Matcher for MatchAll {
+    fn matches(&self, _path: &str) -> bool {
+        true
+    }
+
+    fn visit_children_set(&self, _dir_path: &str) -> VisitChildrenSet {
+        VisitChildrenSet::Recursive
+    }
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains('*') || s.contains('?')
+}
+
+/// Expands a single `{a,b}` alternation inside a path segment, e.g. `Area{1,2}D`
+/// becomes `["Area1D", "Area2D"]`. Only one level of braces is supported.
+fn expand_braces(segment: &str) -> Vec<String> {
+    if let (Some(start), Some(end)) = (segment.find('{'), segment.find('}'))
+        && start < end
+    {
+        let prefix = &segment[..start];
+        let inner = &segment[start + 1..end];
+        let suffix = &segment[end + 1..];
+        return inner
+            .split(',')
+            .map(|alt| format!("{prefix}{alt}{suffix}"))
+            .collect();
+    }
+    vec![segment.to_string()]
+}
+
+fn match_segments(pattern: &[Segment], path: &[&str]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(Segment::DoubleStar) => {
+            if match_segments(&pattern[1..], path) {
+                return true;
+            }
+            match path.split_first() {
+                Some((_, rest)) => match_segments(pattern, rest),
+                None => false,
+            }
+        }
+        Some(Segment::Alternatives(alts)) => match path.split_first() {
+            Some((head, rest)) => {
+                alts.iter().any(|alt| glob_match(alt, head)) && match_segments(&pattern[1..], rest)
+            }
+            None => false,
+        },
+    }
+}
+
+/// Classic `*`/`?` wildcard matching within a single path segment (greedy `*` with
+/// backtracking via the two-pointer algorithm, not a full regex engine).
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let mut star_idx: Option<usize> = None;
+    let mut match_idx = 0usize;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star_idx = Some(pi);
+            match_idx = ti;
+            pi += 1;
+        } else if let Some(si) = star_idx {
+            pi = si + 1;
+            match_idx += 1;
+            ti = match_idx;
+        } else {
+            return false;
+        }
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+fn join_path(parent: &str, name: &str) -> String {
+    if parent == "/" {
+        format!("/{name}")
+    } else {
+        format!("{}/{name}", parent.trim_end_matches('/'))
+    }
+}
+
+/// Walks `node`'s subtree, keeping only the groups and dataset leaves that survive
+/// `matcher`, and reading a group's children from disk only when `visit_children_set`
+/// says they might contain a match. Returns `None` if this node contributes nothing.
+pub(super) fn build_filtered(
+    mapped: &MappedFile,
+    matcher: &dyn Matcher,
+    parent_path: &str,
+    node: RawNode,
+) -> Result<Option<RawNode>> {
+    let path = join_path(parent_path, &node.name);
+
+    if !node.is_group {
+        return Ok(if matcher.matches(&path) {
+            Some(RawNode { path, ..node })
+        } else {
+            None
+        });
+    }
+
+    let visit = matcher.visit_children_set(&path);
+    if visit == VisitChildrenSet::Empty {
+        return Ok(None);
+    }
+
+    let raw_children = if node.children.is_empty() {
+        match node.table_addr {
+            Some(addr) => format::read_children_at(mapped, addr, false)?,
+            None => Vec::new(),
+        }
+    } else {
+        node.children
+    };
+
+    let mut kept = Vec::new();
+    for child in raw_children {
+        let permitted = match &visit {
+            VisitChildrenSet::Recursive | VisitChildrenSet::This => true,
+            VisitChildrenSet::Set(names) => names.contains(&child.name),
+            VisitChildrenSet::Empty => false,
+        };
+        if !permitted {
+            continue;
+        }
+        if let Some(built) = build_filtered(mapped, matcher, &path, child)? {
+            kept.push(built);
+        }
+    }
+
+    if kept.is_empty() && !matcher.matches(&path) {
+        return Ok(None);
+    }
+
+    Ok(Some(RawNode {
+        name: node.name,
+        path,
+        is_group: true,
+        children: kept,
+        attributes: node.attributes,
+        dataset: None,
+        table_addr: node.table_addr,
+    }))
+}
+
+/// Walks the whole file, keeping only nodes `matcher` allows, and returns the
+/// filtered root. Shared by `find_datasets_by_pattern` and, via `MatchAll`, by the
+/// unfiltered `get_file_structure`/`list_datasets` paths.
+pub(super) fn build_filtered_root(mapped: &MappedFile, matcher: &dyn Matcher) -> Result<RawNode> {
+    let root_children = format::read_root_children(mapped)?;
+    let mut kept = Vec::new();
+    for child in root_children {
+        if let Some(built) = build_filtered(mapped, matcher, "/", child)? {
+            kept.push(built);
+        }
+    }
+    Ok(RawNode {
+        name: "/".to_string(),
+        path: "/".to_string(),
+        is_group: true,
+        children: kept,
+        attributes: Default::default(),
+        dataset: None,
+        table_addr: None,
+    })
+}
+
+/// Finds every dataset under an already-open file whose path matches `pattern`
+/// (`*`, `?`, `**`, and `{a,b}` are all supported), pruning subtrees the pattern
+/// can't reach instead of enumerating the whole file.
+pub fn find_datasets_by_pattern(mapped: &MappedFile, pattern: &str) -> Result<Vec<String>> {
+    let matcher = GlobMatcher::compile(pattern);
+    let filtered_root = build_filtered_root(mapped, &matcher)?;
+    let mut out = Vec::new();
+    collect_paths(&filtered_root, &mut out);
+    Ok(out)
+}
+
+fn collect_paths(node: &RawNode, out: &mut Vec<String>) {
+    if node.is_group {
+        for child in &node.children {
+            collect_paths(child, out);
+        }
+    } else {
+        out.push(node.path.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visit_children_set_is_recursive_at_every_depth_past_an_active_double_star() {
+        // A `**` still live three levels deep must keep the whole subtree open, not
+        // just the first level below it (the bug fixed in 556fbd0).
+        let matcher = GlobMatcher::compile("/Results/**/MaxWSE");
+        assert_eq!(matcher.visit_children_set("/Results"), VisitChildrenSet::Recursive);
+        assert_eq!(matcher.visit_children_set("/Results/2D"), VisitChildrenSet::Recursive);
+        assert_eq!(
+            matcher.visit_children_set("/Results/2D/Area 2D"),
+            VisitChildrenSet::Recursive
+        );
+    }
+
+    #[test]
+    fn visit_children_set_prunes_subtrees_that_cant_match() {
+        let matcher = GlobMatcher::compile("/Geometry/2D Flow Areas/Area 2D");
+        assert_eq!(matcher.visit_children_set("/Other"), VisitChildrenSet::Empty);
+    }
+
+    #[test]
+    fn visit_children_set_narrows_to_named_children_without_wildcards() {
+        let matcher = GlobMatcher::compile("/Geometry/{Area 2D,Area 3D}/Cells");
+        let VisitChildrenSet::Set(names) = matcher.visit_children_set("/Geometry") else {
+            panic!("expected a named Set");
+        };
+        assert_eq!(names, HashSet::from(["Area 2D".to_string(), "Area 3D".to_string()]));
+    }
+
+    #[test]
+    fn visit_children_set_is_this_at_an_exact_full_match() {
+        let matcher = GlobMatcher::compile("/Geometry/Area 2D");
+        assert_eq!(
+            matcher.visit_children_set("/Geometry/Area 2D"),
+            VisitChildrenSet::This
+        );
+    }
+
+    #[test]
+    fn visit_children_set_falls_back_to_recursive_for_a_wildcard_segment() {
+        // Can't enumerate a `*` segment's matches without reading names, so pruning
+        // must give up and visit everything rather than guessing wrong.
+        let matcher = GlobMatcher::compile("/Geometry/*/Cells");
+        assert_eq!(matcher.visit_children_set("/Geometry"), VisitChildrenSet::Recursive);
+    }
+
+    #[test]
+    fn matches_handles_double_star_spanning_zero_or_more_segments() {
+        let matcher = GlobMatcher::compile("/Results/**/MaxWSE");
+        assert!(matcher.matches("/Results/MaxWSE"));
+        assert!(matcher.matches("/Results/2D/Area 2D/MaxWSE"));
+        assert!(!matcher.matches("/Results/2D/Area 2D/MaxVel"));
+    }
+
+    #[test]
+    fn matches_expands_brace_alternation() {
+        let matcher = GlobMatcher::compile("/Geometry/{Area 2D,Area 3D}/Cells");
+        assert!(matcher.matches("/Geometry/Area 2D/Cells"));
+        assert!(matcher.matches("/Geometry/Area 3D/Cells"));
+        assert!(!matcher.matches("/Geometry/Area 4D/Cells"));
+    }
+
+    #[test]
+    fn glob_match_supports_star_and_question_mark() {
+        assert!(glob_match("Max*", "MaxWSE"));
+        assert!(glob_match("Max???", "MaxVel"));
+        assert!(!glob_match("Max??", "MaxWSE"));
+        assert!(glob_match("*", "anything"));
+    }
+
+    #[test]
+    fn expand_braces_splits_each_alternative() {
+        assert_eq!(expand_braces("Area{1,2}D"), vec!["Area1D".to_string(), "Area2D".to_string()]);
+        assert_eq!(expand_braces("plain"), vec!["plain".to_string()]);
+    }
+}
@@ -0,0 +1,433 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+mod byteview;
+mod cache;
+mod format;
+mod lock;
+mod matcher;
+mod profile;
+
+use format::MappedFile;
+pub use cache::AnalyzerSession;
+pub use matcher::{GlobMatcher, Matcher, VisitChildrenSet};
+pub use profile::{DatasetGroup, EffectiveProfile, ProfileLayer, UnitConversion};
+
+/// Opens `file_path` for reading, first taking eFlow's cooperative sidecar lock,
+/// which keeps two eFlow reads of the same file apart (see `lock`; it does not
+/// contend with HEC-RAS itself). The lock is held for
+/// as long as the returned `LockedMappedFile` is alive, including if the caller
+/// panics while reading from it. `check_churn` is forwarded to `FileLock::acquire`:
+/// set it only for reads wide enough that a torn mid-write result would actually
+/// matter, since it costs a flat sleep on top of the (cheap) sidecar lock; it is
+/// the check that refuses a file HEC-RAS appears to be writing.
+fn open_locked<P: AsRef<Path>>(file_path: P, check_churn: bool) -> Result<lock::LockedMappedFile> {
+    lock::LockedMappedFile::open(file_path.as_ref(), check_churn).map_err(|e| anyhow::anyhow!(e))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileInfo {
+    pub name: String,
+    pub path: String,
+    pub size_mb: f64,
+    pub modified: DateTime<Utc>,
+    pub accessible: bool,
+    pub groups_count: usize,
+    pub datasets_count: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    pub node_type: String, // "group" or "dataset"
+    pub children: Vec<TreeNode>,
+    pub attributes: HashMap<String, String>,
+    pub shape: Option<Vec<usize>>,
+    pub dtype: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStructure {
+    pub file_path: String,
+    pub root: TreeNode,
+    pub total_groups: usize,
+    pub total_datasets: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HecRasData {
+    pub file: String,
+    /// Extraction-profile group name -> dataset path -> values, with that group's
+    /// `unit_conversion` (if any) already applied.
+    pub groups: HashMap<String, HashMap<String, Vec<f64>>>,
+    pub metadata: HashMap<String, String>,
+    pub extraction_summary: HashMap<String, usize>,
+}
+
+pub struct HDF5Analyzer;
+
+impl HDF5Analyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Obtiene información básica del archivo HDF5. Este peek de groups/datasets
+    /// no toma el lock cooperativo: `find_hdf_files` lo llama para cada archivo de
+    /// una carpeta, y bloquear todo el escaneo porque uno de ellos está en uso por
+    /// una simulación activa sería peor que, en el peor caso, contar de más/de menos
+    /// en ese único archivo (ya tolerado: un error de lectura cuenta como `(0, 0)`).
+    pub fn get_file_info<P: AsRef<Path>>(file_path: P) -> Result<FileInfo> {
+        let path = file_path.as_ref();
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("Failed to read file metadata: {}", path.display()))?;
+
+        let modified = DateTime::from(metadata.modified()
+            .with_context(|| "Failed to get modification time")?);
+
+        let (groups_count, datasets_count) = match MappedFile::open(path).and_then(|m| format::read_tree(&m)) {
+            Ok(root) => count_raw_tree(&root),
+            Err(_) => (0, 0),
+        };
+
+        let info = FileInfo {
+            name: path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: path.to_string_lossy().to_string(),
+            size_mb: metadata.len() as f64 / (1024.0 * 1024.0),
+            modified,
+            accessible: path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext.to_lowercase() == "hdf" || ext.to_lowercase() == "h5")
+                .unwrap_or(false),
+            groups_count,
+            datasets_count,
+            error: if !path.exists() {
+                Some("File does not exist".to_string())
+            } else {
+                None
+            },
+        };
+
+        Ok(info)
+    }
+
+    /// Construye la estructura del archivo HDF5 mapeando el archivo y recorriendo
+    /// sus grupos/datasets nativos. Cuando `pattern` es `Some`, la recorrida se
+    /// filtra y poda en base al patrón (ver `matcher`), evitando leer subárboles
+    /// que no podrían contener una coincidencia; `None` recorre el archivo entero.
+    pub fn get_file_structure<P: AsRef<Path>>(file_path: P, pattern: Option<&str>) -> Result<FileStructure> {
+        let path = file_path.as_ref();
+        if !path.exists() {
+            return Ok(FileStructure {
+                file_path: path.to_string_lossy().to_string(),
+                root: empty_root(),
+                total_groups: 0,
+                total_datasets: 0,
+                error: Some("File does not exist".to_string()),
+            });
+        }
+
+        let locked = open_locked(path, false)?;
+        let mapped = locked.mapped();
+        let raw_root = match pattern {
+            Some(pattern) => matcher::build_filtered_root(mapped, &GlobMatcher::compile(pattern))?,
+            None => matcher::build_filtered_root(mapped, &matcher::MatchAll)?,
+        };
+        let root = raw_to_tree_node(&raw_root, "");
+        let (total_groups, total_datasets) = count_raw_tree(&raw_root);
+
+        Ok(FileStructure {
+            file_path: path.to_string_lossy().to_string(),
+            root,
+            total_groups,
+            total_datasets,
+            error: None,
+        })
+    }
+
+    /// Lista todos los datasets en el archivo
+    pub fn list_datasets<P: AsRef<Path>>(file_path: P) -> Result<Vec<String>> {
+        let locked = open_locked(file_path, false)?;
+        let raw_root = matcher::build_filtered_root(locked.mapped(), &matcher::MatchAll)?;
+        let mut paths = Vec::new();
+        collect_dataset_paths(&raw_root, "", &mut paths);
+        Ok(paths)
+    }
+
+    /// Lista los datasets cuyo path coincide con `pattern` (`*`, `?`, `**`, y
+    /// alternación `{a,b}`), podando subárboles enteros que el patrón no puede
+    /// alcanzar en vez de enumerar todo el archivo.
+    pub fn find_datasets_by_pattern<P: AsRef<Path>>(file_path: P, pattern: &str) -> Result<Vec<String>> {
+        let locked = open_locked(file_path, false)?;
+        matcher::find_datasets_by_pattern(locked.mapped(), pattern)
+    }
+
+    /// Extrae datos de HEC-RAS según el perfil de extracción efectivo del archivo
+    /// (ver `profile`): cada grupo de datasets se resuelve vía glob contra el
+    /// archivo, se lee completo, y se le aplica la conversión de unidades del grupo
+    /// si tiene una. Antes de leer, rechaza el archivo si su tamaño/mtime cambia
+    /// (señal de que HEC-RAS lo está escribiendo) y toma el lock cooperativo de
+    /// eFlow durante la lectura (ver `lock`).
+    pub fn extract_hecras_data<P: AsRef<Path>>(file_path: P) -> Result<HecRasData> {
+        let path = file_path.as_ref();
+        let profile = profile::load_effective_profile(path)?;
+        // This is the one path worth the churn-check's flat sleep: it reads every
+        // dataset in the profile in full, so a torn mid-write read here is both
+        // likely and the most expensive kind to have silently produced.
+        let locked = open_locked(path, true)?;
+        let mapped = locked.mapped();
+
+        let mut hecras_data = HecRasData {
+            file: path.to_string_lossy().to_string(),
+            groups: HashMap::new(),
+            metadata: HashMap::new(),
+            extraction_summary: HashMap::new(),
+        };
+
+        let mut datasets_extracted = 0usize;
+        for (group_name, group) in &profile.dataset_groups {
+            let mut group_data = HashMap::new();
+            for pattern in &group.patterns {
+                let dataset_paths = matcher::find_datasets_by_pattern(mapped, pattern).unwrap_or_default();
+                for dataset_path in dataset_paths {
+                    if let Ok(mut values) = read_full_dataset(mapped, &dataset_path) {
+                        if let Some(conversion) = &group.unit_conversion {
+                            for value in &mut values {
+                                *value = *value * conversion.scale + conversion.offset;
+                            }
+                        }
+                        group_data.insert(dataset_path, values);
+                    }
+                }
+            }
+            datasets_extracted += group_data.len();
+            hecras_data.groups.insert(group_name.clone(), group_data);
+        }
+
+        if let Ok(root) = format::locate_node(mapped, "/") {
+            match &profile.metadata_attributes {
+                // `None`: no layer narrowed the set, so surface every root attribute.
+                None => hecras_data.metadata.extend(root.attributes),
+                // `Some(names)`: surface only those names — `Some(vec![])` means a
+                // layer explicitly asked for none.
+                Some(names) => hecras_data.metadata.extend(
+                    root.attributes.into_iter().filter(|(name, _)| names.contains(name)),
+                ),
+            }
+        }
+
+        hecras_data.extraction_summary.insert("groups".to_string(), hecras_data.groups.len());
+        hecras_data.extraction_summary.insert("datasets".to_string(), datasets_extracted);
+        hecras_data.extraction_summary.insert("metadata_items".to_string(), hecras_data.metadata.len());
+
+        Ok(hecras_data)
+    }
+
+    /// Reports the effective extraction profile for `file_path` (built-in defaults
+    /// layered with `~/.eflow/extraction_profile.json` and the project's
+    /// `eflow_profile.json`), with provenance for every field.
+    pub fn list_extraction_profile<P: AsRef<Path>>(file_path: P) -> Result<profile::EffectiveProfile> {
+        profile::load_effective_profile(file_path.as_ref())
+    }
+
+    /// Lee un dataset específico y devuelve una muestra de los datos. El archivo se
+    /// mapea entero, pero solo se leen los bytes del prefijo de ese dataset.
+    pub fn read_dataset_sample<P: AsRef<Path>>(file_path: P, dataset_path: &str, max_elements: usize) -> Result<Vec<f64>> {
+        let locked = open_locked(file_path, false)?;
+        let mapped = locked.mapped();
+        let node = format::locate_node(mapped, dataset_path)
+            .with_context(|| format!("dataset not found: {dataset_path}"))?;
+        let location = node
+            .dataset
+            .with_context(|| format!("{dataset_path} is a group, not a dataset"))?;
+        if let Some(reason) = &location.unsupported_reason {
+            anyhow::bail!("{dataset_path}: {reason}");
+        }
+        location.read_sample(mapped, max_elements)
+    }
+
+    /// Recorre un dataset completo elemento a elemento sin materializarlo en un
+    /// `Vec`, para consumidores (p.ej. exportadores) que solo necesitan procesar
+    /// cada valor una vez. El archivo completo se mapea, pero los elementos se
+    /// decodifican de a uno por iteración en vez de copiarse todos por adelantado.
+    pub fn for_each_dataset_value<P: AsRef<Path>>(
+        file_path: P,
+        dataset_path: &str,
+        mut visit: impl FnMut(f64),
+    ) -> Result<()> {
+        let locked = open_locked(file_path, false)?;
+        let mapped = locked.mapped();
+        let node = format::locate_node(mapped, dataset_path)
+            .with_context(|| format!("dataset not found: {dataset_path}"))?;
+        let location = node
+            .dataset
+            .with_context(|| format!("{dataset_path} is a group, not a dataset"))?;
+        if let Some(reason) = &location.unsupported_reason {
+            anyhow::bail!("{dataset_path}: {reason}");
+        }
+        for value in location.read_stream(mapped)? {
+            visit(value);
+        }
+        Ok(())
+    }
+
+    /// Busca archivos HDF5 en una carpeta
+    pub fn find_hdf_files<P: AsRef<Path>>(folder_path: P) -> Result<Vec<FileInfo>> {
+        let path = folder_path.as_ref();
+        let mut hdf_files = Vec::new();
+
+        if !path.exists() {
+            return Err(anyhow::anyhow!("Folder does not exist: {}", path.display()));
+        }
+
+        if !path.is_dir() {
+            return Err(anyhow::anyhow!("Path is not a directory: {}", path.display()));
+        }
+
+        // Buscar archivos recursivamente
+        Self::search_hdf_files_recursive(path, &mut hdf_files)?;
+
+        // Ordenar por nombre de archivo
+        hdf_files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(hdf_files)
+    }
+
+    /// Busca archivos HDF recursivamente
+    fn search_hdf_files_recursive(dir: &Path, files: &mut Vec<FileInfo>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.is_dir() {
+                // Buscar recursivamente en subdirectorios
+                Self::search_hdf_files_recursive(&path, files)?;
+            } else if path.is_file() {
+                // Verificar si es un archivo HDF
+                if let Some(extension) = path.extension() {
+                    let ext = extension.to_string_lossy().to_lowercase();
+                    if ext == "hdf" || ext == "h5" || ext == "hdf5" {
+                        match Self::get_file_info(&path) {
+                            Ok(file_info) => files.push(file_info),
+                            Err(e) => {
+                                // Crear un FileInfo con error para archivos que no se pueden leer
+                                let file_info = FileInfo {
+                                    name: path.file_name()
+                                        .and_then(|n| n.to_str())
+                                        .unwrap_or("unknown")
+                                        .to_string(),
+                                    path: path.to_string_lossy().to_string(),
+                                    size_mb: 0.0,
+                                    modified: chrono::Utc::now(),
+                                    accessible: false,
+                                    groups_count: 0,
+                                    datasets_count: 0,
+                                    error: Some(format!("Error reading file: {}", e)),
+                                };
+                                files.push(file_info);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_full_dataset(mapped: &MappedFile, dataset_path: &str) -> Result<Vec<f64>> {
+    let node = format::locate_node(mapped, dataset_path)?;
+    let location = node
+        .dataset
+        .with_context(|| format!("{dataset_path} is a group, not a dataset"))?;
+    if let Some(reason) = &location.unsupported_reason {
+        anyhow::bail!("{dataset_path}: {reason}");
+    }
+    Ok(location.read_stream(mapped)?.collect())
+}
+
+fn empty_root() -> TreeNode {
+    TreeNode {
+        name: "/".to_string(),
+        path: "/".to_string(),
+        node_type: "group".to_string(),
+        children: Vec::new(),
+        attributes: HashMap::new(),
+        shape: None,
+        dtype: None,
+    }
+}
+
+fn raw_to_tree_node(node: &format::RawNode, parent_path: &str) -> TreeNode {
+    let path = if parent_path.is_empty() {
+        if node.name == "/" { "/".to_string() } else { format!("/{}", node.name) }
+    } else {
+        format!("{}/{}", parent_path.trim_end_matches('/'), node.name)
+    };
+
+    let children = node
+        .children
+        .iter()
+        .map(|c| raw_to_tree_node(c, &path))
+        .collect();
+
+    let (shape, dtype) = match &node.dataset {
+        Some(ds) => (
+            Some(ds.shape.clone()),
+            Some(match ds.element_kind {
+                Some(k) => format!("{k:?}"),
+                None => ds
+                    .unsupported_reason
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+            }),
+        ),
+        None => (None, None),
+    };
+
+    TreeNode {
+        name: node.name.clone(),
+        path,
+        node_type: if node.is_group { "group" } else { "dataset" }.to_string(),
+        children,
+        attributes: node.attributes.clone(),
+        shape,
+        dtype,
+    }
+}
+
+fn count_raw_tree(node: &format::RawNode) -> (usize, usize) {
+    let mut groups = if node.is_group { 1 } else { 0 };
+    let mut datasets = if node.is_group { 0 } else { 1 };
+    for child in &node.children {
+        let (g, d) = count_raw_tree(child);
+        groups += g;
+        datasets += d;
+    }
+    (groups, datasets)
+}
+
+fn collect_dataset_paths(node: &format::RawNode, parent_path: &str, out: &mut Vec<String>) {
+    let path = if parent_path.is_empty() {
+        if node.name == "/" { "/".to_string() } else { format!("/{}", node.name) }
+    } else {
+        format!("{}/{}", parent_path.trim_end_matches('/'), node.name)
+    };
+
+    if node.is_group {
+        for child in &node.children {
+            collect_dataset_paths(child, &path, out);
+        }
+    } else {
+        out.push(path);
+    }
+}
@@ -0,0 +1,172 @@
+//! Layered extraction-profile configuration: which dataset groups
+//! `extract_hecras_data` pulls, what unit conversions apply to each, and which
+//! root metadata attributes to surface.
+//!
+//! Three layers are merged in increasing priority, each overriding same-named
+//! entries from the one before:
+//!   1. the built-in default (the `/Geometry/...`/`/Results/...` datasets eFlow has
+//!      always looked for),
+//!   2. a user-global file at `~/.eflow/extraction_profile.json`,
+//!   3. a per-project file named `eflow_profile.json` next to the HDF file, so a
+//!      whole HEC-RAS project folder can share one profile.
+//!
+//! A missing layer file is not an error — it just contributes nothing. A layer file
+//! that exists but fails to parse is, so a typo doesn't silently fall back to
+//! defaults.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// A named group of datasets (matched by glob pattern, see `matcher`) with an
+/// optional linear unit conversion applied to every value read from them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetGroup {
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub unit_conversion: Option<UnitConversion>,
+}
+
+/// `value * scale + offset`, e.g. feet-to-meters is `{ scale: 0.3048, offset: 0.0,
+/// unit: "m" }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitConversion {
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+    pub unit: String,
+}
+
+/// Which layer last set a given field of the effective profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProfileLayer {
+    Default,
+    UserGlobal,
+    Project,
+}
+
+/// The three layers merged into one, plus provenance for every field so a caller
+/// (or `list_extraction_profiles`) can show the user where each value came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectiveProfile {
+    pub dataset_groups: HashMap<String, DatasetGroup>,
+    /// `None` surfaces every root attribute; `Some(names)` surfaces only those
+    /// names (including `Some(vec![])`, which surfaces none) — see `RawProfile`.
+    pub metadata_attributes: Option<Vec<String>>,
+    /// Keyed by dataset group name, plus the literal key `"metadata_attributes"`.
+    pub provenance: HashMap<String, ProfileLayer>,
+}
+
+/// One layer's worth of config as read from disk — every field optional, since a
+/// layer need only override the pieces it cares about.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RawProfile {
+    #[serde(default)]
+    dataset_groups: HashMap<String, DatasetGroup>,
+    #[serde(default)]
+    metadata_attributes: Option<Vec<String>>,
+}
+
+const GEOMETRY_DATASETS: &[&str] = &[
+    "/Geometry/2D Flow Areas/Area 2D/Cells Center Coordinate",
+    "/Geometry/2D Flow Areas/Area 2D/Cells FacePoint Indexes",
+];
+const RESULTS_DATASETS: &[&str] = &[
+    "/Results/2D/MaxWSE",
+    "/Results/2D/MaxVel",
+    "/Results/2D/MaxDepth",
+];
+
+fn default_profile() -> RawProfile {
+    let mut dataset_groups = HashMap::new();
+    dataset_groups.insert(
+        "geometry".to_string(),
+        DatasetGroup {
+            patterns: GEOMETRY_DATASETS.iter().map(|s| s.to_string()).collect(),
+            unit_conversion: None,
+        },
+    );
+    dataset_groups.insert(
+        "results".to_string(),
+        DatasetGroup {
+            patterns: RESULTS_DATASETS.iter().map(|s| s.to_string()).collect(),
+            unit_conversion: None,
+        },
+    );
+    // `None` means "surface every root attribute" (the historical behavior); a user
+    // or project layer narrows this by setting `Some(names)` — including
+    // `Some(vec![])`, which means "surface none".
+    RawProfile { dataset_groups, metadata_attributes: None }
+}
+
+fn user_global_profile_path() -> PathBuf {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(".eflow").join("extraction_profile.json")
+}
+
+fn project_profile_path(hdf_file_path: &Path) -> PathBuf {
+    hdf_file_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("eflow_profile.json")
+}
+
+/// Modification times of the user-global and project profile layers for
+/// `hdf_file_path` (`None` for a layer that doesn't exist).
+pub(crate) type ProfileFingerprint = (Option<std::time::SystemTime>, Option<std::time::SystemTime>);
+
+/// Lets a cache invalidate itself when a profile file changes even though the HDF5
+/// file it's paired with didn't.
+pub(crate) fn layer_fingerprint(hdf_file_path: &Path) -> ProfileFingerprint {
+    let mtime = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    (mtime(&user_global_profile_path()), mtime(&project_profile_path(hdf_file_path)))
+}
+
+fn load_layer_file(path: &Path) -> Result<Option<RawProfile>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read extraction profile: {}", path.display()))?;
+    let profile: RawProfile = serde_json::from_str(&contents)
+        .with_context(|| format!("malformed extraction profile: {}", path.display()))?;
+    Ok(Some(profile))
+}
+
+/// Loads and merges all three layers for the HDF file at `hdf_file_path`.
+pub fn load_effective_profile(hdf_file_path: &Path) -> Result<EffectiveProfile> {
+    let mut dataset_groups = HashMap::new();
+    let mut metadata_attributes = None;
+    let mut provenance = HashMap::new();
+
+    let mut apply = |layer: ProfileLayer, raw: RawProfile| {
+        for (name, group) in raw.dataset_groups {
+            dataset_groups.insert(name.clone(), group);
+            provenance.insert(name, layer);
+        }
+        if let Some(attrs) = raw.metadata_attributes {
+            metadata_attributes = Some(attrs);
+            provenance.insert("metadata_attributes".to_string(), layer);
+        }
+    };
+
+    // The default layer's `metadata_attributes` is itself `None` ("surface
+    // everything"), so it can't go through `apply`'s `if let Some(attrs)` above —
+    // that only fires when a layer actually narrows the set. Set its provenance
+    // directly so `metadata_attributes` always has an entry even when no layer
+    // ever overrides the default.
+    provenance.insert("metadata_attributes".to_string(), ProfileLayer::Default);
+    apply(ProfileLayer::Default, default_profile());
+    if let Some(raw) = load_layer_file(&user_global_profile_path())? {
+        apply(ProfileLayer::UserGlobal, raw);
+    }
+    if let Some(raw) = load_layer_file(&project_profile_path(hdf_file_path))? {
+        apply(ProfileLayer::Project, raw);
+    }
+
+    Ok(EffectiveProfile { dataset_groups, metadata_attributes, provenance })
+}
@@ -16,20 +16,57 @@ async fn analyze_hdf5_info(file_path: String) -> Result<hdf5_analyzer::FileInfo,
 }
 
 #[tauri::command]
-async fn analyze_hdf5_structure(file_path: String) -> Result<hdf5_analyzer::FileStructure, String> {
-    hdf5_analyzer::HDF5Analyzer::get_file_structure(&file_path)
+async fn analyze_hdf5_structure(
+    file_path: String,
+    pattern: Option<String>,
+    bypass_cache: Option<bool>,
+    session: tauri::State<'_, hdf5_analyzer::AnalyzerSession>,
+) -> Result<hdf5_analyzer::FileStructure, String> {
+    session
+        .get_file_structure(&file_path, pattern.as_deref(), bypass_cache.unwrap_or(false))
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn list_hdf5_datasets(file_path: String) -> Result<Vec<String>, String> {
-    hdf5_analyzer::HDF5Analyzer::list_datasets(&file_path)
+async fn list_hdf5_datasets(
+    file_path: String,
+    bypass_cache: Option<bool>,
+    session: tauri::State<'_, hdf5_analyzer::AnalyzerSession>,
+) -> Result<Vec<String>, String> {
+    session
+        .list_datasets(&file_path, bypass_cache.unwrap_or(false))
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn extract_hecras_data(file_path: String) -> Result<hdf5_analyzer::HecRasData, String> {
-    hdf5_analyzer::HDF5Analyzer::extract_hecras_data(&file_path)
+async fn invalidate_hdf5_cache(
+    file_path: String,
+    session: tauri::State<'_, hdf5_analyzer::AnalyzerSession>,
+) -> Result<(), String> {
+    session.invalidate(&file_path);
+    Ok(())
+}
+
+#[tauri::command]
+async fn find_hdf5_datasets_by_pattern(file_path: String, pattern: String) -> Result<Vec<String>, String> {
+    hdf5_analyzer::HDF5Analyzer::find_datasets_by_pattern(&file_path, &pattern)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn extract_hecras_data(
+    file_path: String,
+    bypass_cache: Option<bool>,
+    session: tauri::State<'_, hdf5_analyzer::AnalyzerSession>,
+) -> Result<hdf5_analyzer::HecRasData, String> {
+    session
+        .extract_hecras_data(&file_path, bypass_cache.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn list_extraction_profiles(file_path: String) -> Result<hdf5_analyzer::EffectiveProfile, String> {
+    hdf5_analyzer::HDF5Analyzer::list_extraction_profile(&file_path)
         .map_err(|e| e.to_string())
 }
 
@@ -65,14 +102,18 @@ pub mod ext_mod {
                 let builder = tauri::Builder::default()
                     .plugin(tauri_plugin_opener::init())
                     .plugin(tauri_plugin_dialog::init())
+                    .manage(hdf5_analyzer::AnalyzerSession::new())
                     .invoke_handler(tauri::generate_handler![
                         greet,
                         analyze_hdf5_info,
                         analyze_hdf5_structure,
                         list_hdf5_datasets,
+                        find_hdf5_datasets_by_pattern,
                         extract_hecras_data,
+                        list_extraction_profiles,
                         read_hdf5_dataset_sample,
-                        find_hdf5_files
+                        find_hdf5_files,
+                        invalidate_hdf5_cache
                     ]);
                 Ok(builder)
             },